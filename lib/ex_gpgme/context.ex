@@ -8,9 +8,23 @@ defmodule ExGpgme.Context do
 
   use Rustler, otp_app: :ex_gpgme, crate: :exgpgme
 
-  alias ExGpgme.Results.{ImportResult, VerificationResult}
-  alias ExGpgme.Keys.Key
+  alias ExGpgme.Results.{
+    DecryptionResult,
+    EncryptionResult,
+    ImportResult,
+    KeyGenerationResult,
+    SigningResult,
+    VerificationResult
+  }
+  alias ExGpgme.Keys
+  alias ExGpgme.Keys.{Key, Subkey, UserId}
+  alias ExGpgme.CreateKeyFlags
+  alias ExGpgme.DecryptFlags
   alias ExGpgme.EncryptFlags
+  alias ExGpgme.Error
+  alias ExGpgme.ExportMode
+  alias ExGpgme.KeylistMode
+  alias ExGpgme.KeySigningFlags
   alias ExGpgme.Engine.EngineInfo
 
   @typedoc """
@@ -21,6 +35,52 @@ defmodule ExGpgme.Context do
   """
   @opaque context :: reference
 
+  @typedoc """
+  Cursor over a key listing. See `open_key_cursor/3`.
+  """
+  @opaque key_cursor :: reference
+
+  @typedoc """
+  Streaming session of a cryptographic operation. See `open_session/2`.
+  """
+  @opaque session :: reference
+
+  @typedoc """
+  Operation running in the background. See `encrypt_async/4`.
+  """
+  @opaque operation :: reference
+
+  @typedoc """
+  Message sent to the caller when an operation started by `encrypt_async/4`, `decrypt_async/2`, `sign_async/3` or
+  `verify_async/2` is done. The result contains the output of the operation like `finish_session/1`.
+  """
+  @type operation_reply ::
+          {:gpgme_result, operation, {:ok, binary, session_result} | {:error, Error.t()}}
+
+  @typedoc """
+  Pending passphrase request. See `set_passphrase_provider/3`.
+  """
+  @opaque passphrase_request :: reference
+
+  @typedoc """
+  Operation performed by a session.
+  """
+  @type session_operation ::
+          {:encrypt, [Key.resource()], EncryptFlags.flags()}
+          | :decrypt
+          | {:sign, ExGpgme.sign_mode()}
+          | :verify
+
+  @typedoc """
+  Result of a finished session. `encrypt` sessions return the `EncryptionResult`, `decrypt` sessions the
+  `DecryptionResult`, `sign` sessions the `SigningResult` and `verify` sessions the `VerificationResult`.
+  """
+  @type session_result ::
+          EncryptionResult.t()
+          | DecryptionResult.t()
+          | SigningResult.t()
+          | VerificationResult.t()
+
   @doc """
   The function creates a context with the protocol. All crypto operations will be performed by the crypto engine
   configured for that protocol.
@@ -34,7 +94,7 @@ defmodule ExGpgme.Context do
       iex> ExGpgme.Context.from_protocol(:open_pgp)
       {:ok, #Reference<0.1689386418.123076612.191614>}
   """
-  @spec from_protocol(protocol :: ExGpgme.protocol()) :: {:ok, context} | {:error, String.t()}
+  @spec from_protocol(protocol :: ExGpgme.protocol()) :: {:ok, context} | {:error, Error.t()}
   def from_protocol(_protocol), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -171,16 +231,17 @@ defmodule ExGpgme.Context do
   def set_offline(_context, _yes), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
-  The value of flags settable by `set_flag/3` can be retrieved by this function. If name is unknown the function returns
-  `:error`. For boolean flags an empty string is returned for `false` and the string `"1"` is returned for `true`;
-  a test for an empty string can be used to get the boolean value.
+  The value of flags settable by `set_flag/3` can be retrieved by this function. If name is unknown or the flag is not
+  set the function returns an error with the code `:not_found`. For boolean flags an empty string is returned for
+  `false` and the string `"1"` is returned for `true`; a test for an empty string can be used to get the boolean value.
 
   ### Examples
 
       iex> :open_pgp
       ...> |> ExGpgme.Context.from_protocol!
       ...> |> ExGpgme.Context.get_flag("not-existing-flag")
-      {:error, :not_set}
+      {:error,
+       %ExGpgme.Error{code: :not_found, source: :unknown, raw: 27, message: "Flag is not set"}}
 
       iex> :open_pgp
       ...> |> ExGpgme.Context.from_protocol!
@@ -188,7 +249,7 @@ defmodule ExGpgme.Context do
       {:ok, ""}
   """
   @spec get_flag(context :: context, name :: String.t()) ::
-          {:ok, String.t()} | {:error, :not_set | String.t()}
+          {:ok, String.t()} | {:error, Error.t()}
   def get_flag(_context, _name), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -227,7 +288,8 @@ defmodule ExGpgme.Context do
       iex> context = :open_pgp
       ...> |> ExGpgme.Context.from_protocol!
       iex> ExGpgme.Context.set_flag(context, "not-existing-flag", "1")
-      {:error, "Unknown name"}
+      {:error,
+       %ExGpgme.Error{code: :unknown_name, source: :gpgme, raw: 117440677, message: "Unknown name"}}
 
       iex> context = :open_pgp
       ...> |> ExGpgme.Context.from_protocol!
@@ -235,7 +297,7 @@ defmodule ExGpgme.Context do
       :ok
   """
   @spec set_flag(context :: context, flag :: String.t(), value :: String.t()) ::
-          :ok | {:error, String.t()}
+          :ok | {:error, Error.t()}
   def set_flag(_context, _flag, _value), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -250,7 +312,7 @@ defmodule ExGpgme.Context do
         path: "/usr/local/MacGPG2/bin/gpg", protocol: :open_pgp,
         required_version: "1.4.0", version: "2.2.0"}
   """
-  @spec engine_info(context :: context) :: {:ok, EngineInfo.t()} | {:error, String.t()}
+  @spec engine_info(context :: context) :: {:ok, EngineInfo.t()} | {:error, Error.t()}
   def engine_info(_context), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -274,7 +336,7 @@ defmodule ExGpgme.Context do
       ...> |> ExGpgme.Context.set_engine_path("/some/path")
       :ok
   """
-  @spec set_engine_path(context :: context, path :: String.t()) :: :ok | {:error, String.t()}
+  @spec set_engine_path(context :: context, path :: String.t()) :: :ok | {:error, Error.t()}
   def set_engine_path(_context, _path), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -299,7 +361,7 @@ defmodule ExGpgme.Context do
       :ok
   """
   @spec set_engine_home_dir(context :: context, home_dir :: String.t()) ::
-          :ok | {:error, String.t()}
+          :ok | {:error, Error.t()}
   def set_engine_home_dir(_context, _home_dir), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -340,7 +402,7 @@ defmodule ExGpgme.Context do
       :ok
   """
   @spec set_pinentry_mode(context :: context, mode :: ExGpgme.pinentry_mode()) ::
-          :ok | {:error, String.t()}
+          :ok | {:error, Error.t()}
   def set_pinentry_mode(_context, _mode), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
@@ -354,6 +416,232 @@ defmodule ExGpgme.Context do
     end
   end
 
+  @doc """
+  The function returns the keylist mode set for the context. The keylist mode influences the key listing functions
+  like `find_key/2` and `list_keys/3`.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.keylist_mode
+      [:local]
+  """
+  @spec keylist_mode(context :: context) :: KeylistMode.modes()
+  def keylist_mode(context), do: get_keylist_mode(context)
+
+  @spec get_keylist_mode(context :: context) :: KeylistMode.modes()
+  defp get_keylist_mode(_context), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  The function sets the keylist mode for the context. The following modes can be combined:
+
+  * `:local` - Specifies that the local keyring should be searched for keys in the keylisting operation.
+  * `:extern` - Specifies that an external source should be searched for keys in the keylisting operation. The type
+    of external source is dependent on the crypto engine used and whether it is combined with `:local`.
+  * `:locate` - Shortcut for the combination of `:local` and `:extern`.
+  * `:sigs` - Specifies that the key signatures should be included in the listed keys.
+  * `:sig_notations` - Specifies that the signature notations on key signatures should be included in the listed
+    keys. This only works if `:sigs` is also enabled.
+  * `:with_secret` - Returns information about the presence of a corresponding secret key in a public key listing.
+  * `:with_tofu` - Specifies that information about the TOFU status of the user ids should be included.
+  * `:ephemeral` - Specifies that keys flagged as ephemeral are included in the listing.
+  * `:validate` - Specifies that a backend should do key or certificate validation and not just get the validity
+    information from an internal cache.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.set_keylist_mode([:local, :with_secret])
+      :ok
+  """
+  @spec set_keylist_mode(context :: context, modes :: KeylistMode.modes()) ::
+          :ok | {:error, Error.t()}
+  def set_keylist_mode(_context, _modes), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `set_keylist_mode/2`
+  """
+  @spec set_keylist_mode!(context :: context, modes :: KeylistMode.modes()) :: nil | no_return
+  def set_keylist_mode!(context, modes) do
+    case set_keylist_mode(context, modes) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Adds `key` to the list of signers of the context. Signing operations create one signature for every signer. If no
+  signer is set, the default key of the engine is used.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.add_signer(context, key)
+      :ok
+  """
+  @spec add_signer(context :: context, key :: Key.resource()) :: :ok | {:error, Error.t()}
+  def add_signer(_context, _key), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `add_signer/2`
+  """
+  @spec add_signer!(context :: context, key :: Key.resource()) :: nil | no_return
+  def add_signer!(context, key) do
+    case add_signer(context, key) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Removes all signers from the context.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.clear_signers
+      :ok
+  """
+  @spec clear_signers(context :: context) :: :ok
+  def clear_signers(_context), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Returns the signers of the context. See `add_signer/2`.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.signers
+      []
+  """
+  @spec signers(context :: context) :: [Key.resource()]
+  def signers(_context), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Routes passphrase requests of the context to `pid`. Operations that need a passphrase send
+
+      {:gpgme_passphrase, request, uid_hint, info, prev_was_bad}
+
+  to `pid` and block until `reply_passphrase/2` is called with the request or `timeout` milliseconds have passed.
+  `uid_hint` and `info` are strings or `nil`, `prev_was_bad` is `true` if the last passphrase was wrong.
+
+  The operation blocks the calling process and a dirty scheduler while it waits, so `pid` has to be a different
+  process that replies promptly. If no reply arrives within `timeout`, the operation fails with an `ExGpgme.Error`
+  with code `:timeout` and later replies to the request return an error. Replying with `nil` fails the operation with
+  code `:canceled`. gpg only asks for passphrases if the pinentry mode is `:loopback`, see `set_pinentry_mode/2`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> pid = spawn(fn -> :ok end)
+      iex> ExGpgme.Context.set_passphrase_provider(context, pid)
+      :ok
+  """
+  @spec set_passphrase_provider(context :: context, pid :: pid, timeout :: non_neg_integer) :: :ok
+  def set_passphrase_provider(_context, _pid, _timeout \\ 5_000),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Removes the passphrase provider of the context. See `set_passphrase_provider/3`.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.clear_passphrase_provider
+      :ok
+  """
+  @spec clear_passphrase_provider(context :: context) :: :ok
+  def clear_passphrase_provider(_context), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Answers a passphrase request sent by the provider. Replying with `nil` cancels the operation. A request can only be
+  answered once and not after it timed out.
+
+  ### Examples
+
+      receive do
+        {:gpgme_passphrase, request, _uid_hint, _info, _prev_was_bad} ->
+          ExGpgme.Context.reply_passphrase(request, "secret")
+      end
+  """
+  @spec reply_passphrase(request :: passphrase_request, passphrase :: String.t() | nil) ::
+          :ok | {:error, Error.t()}
+  def reply_passphrase(_request, _passphrase), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Sends progress of the operations of the context to `pid`. Long running operations like key generation or the
+  encryption of large data report
+
+      {:gpgme_progress, origin, what, type, current, total}
+
+  where `origin` is the context for synchronous operations and sessions, or the `operation` returned by the `*_async`
+  functions. `what` is a string or `nil` and `type`, `current` and `total` are integers. `total` is `0` if it is
+  unknown.
+  Unlike the passphrase provider, the calling process can receive the messages itself. The messages are delivered
+  asynchronously and may arrive shortly after the operation returned.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.set_progress_handler(self())
+      :ok
+  """
+  @spec set_progress_handler(context :: context, pid :: pid) :: :ok
+  def set_progress_handler(_context, _pid \\ self()), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Stops sending progress of the operations of the context. See `set_progress_handler/2`.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.clear_progress_handler
+      :ok
+  """
+  @spec clear_progress_handler(context :: context) :: :ok
+  def clear_progress_handler(_context), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Forwards the raw `[GNUPG:]` status lines of the operations of the context to `pid` as
+
+      {:gpgme_status, origin, keyword, args}
+
+  where `origin` is the context or the background operation like for progress, `keyword` is the status keyword like
+  `"KEY_CONSIDERED"` or `"NO_PUBKEY"` and `args` the rest of the line. All status lines except progress are forwarded,
+  see `set_progress_handler/2` for those. Like progress, the messages may arrive shortly after the operation returned.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.set_status_handler(self())
+      :ok
+  """
+  @spec set_status_handler(context :: context, pid :: pid) :: :ok
+  def set_status_handler(_context, _pid \\ self()), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Stops forwarding status lines of the operations of the context. See `set_status_handler/2`.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.clear_status_handler
+      :ok
+  """
+  @spec clear_status_handler(context :: context) :: :ok
+  def clear_status_handler(_context), do: :erlang.nif_error(:nif_not_loaded)
+
   @doc """
   Import Keys
 
@@ -371,14 +659,14 @@ defmodule ExGpgme.Context do
         secret_imported: 0, secret_unchanged: 0, unchanged: 1,
         without_user_id: 0}}
   """
-  @spec import(context :: context, data :: String.t()) ::
-          {:ok, ImportResult.t()} | {:error, String.t()}
+  @spec import(context :: context, data :: binary) ::
+          {:ok, ImportResult.t()} | {:error, Error.t()}
   def import(_context, _data), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
   See `import/2`.
   """
-  @spec import!(context :: context, data :: String.t()) :: ImportResult.t() | no_return
+  @spec import!(context :: context, data :: binary) :: ImportResult.t() | no_return
   def import!(context, data) do
     case __MODULE__.import(context, data) do
       {:ok, result} -> result
@@ -386,6 +674,44 @@ defmodule ExGpgme.Context do
     end
   end
 
+  @doc """
+  Export Keys
+
+  `keys` is either a list of keys or a list of patterns. An empty list exports all keys.
+
+  The output is ASCII armored if `set_armor/2` is enabled and binary otherwise.
+  With the `:extern` mode the keys are sent to the keyserver and an empty binary is returned.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.export_keys(context, ["sender@example.com"], [:minimal])
+      {:ok, "-----BEGIN PGP PUBLIC KEY BLOCK-----\n..."}
+  """
+  @spec export_keys(
+          context :: context,
+          keys :: [Key.resource()] | [String.t()],
+          mode :: ExportMode.modes()
+        ) :: {:ok, binary} | {:error, Error.t()}
+  def export_keys(_context, _keys \\ [], _mode \\ []), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `export_keys/3`.
+  """
+  @spec export_keys!(
+          context :: context,
+          keys :: [Key.resource()] | [String.t()],
+          mode :: ExportMode.modes()
+        ) :: binary | no_return
+  def export_keys!(context, keys \\ [], mode \\ []) do
+    case export_keys(context, keys, mode) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
   @doc """
   Find a key by Fingerprint
 
@@ -399,16 +725,16 @@ defmodule ExGpgme.Context do
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
       iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
       iex> ExGpgme.Context.find_key(context, "not-existing-fingerprint")
-      {:error, "End of file"}
+      {:error, %ExGpgme.Error{code: :eof, source: :gpgme, raw: 117456895, message: "End of file"}}
   """
   @spec find_key(context :: context, fingerprint :: String.t()) ::
-          {:ok, Key.t()} | {:error, String.t()}
+          {:ok, Key.resource()} | {:error, Error.t()}
   def find_key(_context, _fingerprint), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
   See `find_key/2`.
   """
-  @spec find_key!(context :: context, fingerprint :: String.t()) :: Key.t() | no_return
+  @spec find_key!(context :: context, fingerprint :: String.t()) :: Key.resource() | no_return
   def find_key!(context, fingerprint) do
     case find_key(context, fingerprint) do
       {:ok, result} -> result
@@ -417,212 +743,1331 @@ defmodule ExGpgme.Context do
   end
 
   @doc """
-  Encrypts a message for the specified recipients.
+  Returns a `Key` struct that describes the key `key`.
 
   ### Examples
 
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
-      iex> ExGpgme.Context.set_armor(context, true)
       iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
-      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
-      iex> ExGpgme.Context.encrypt(context, [recipient], "Hello World", [:always_trust])
+      iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.key_info(key)
       {:ok,
-       "-----BEGIN PGP MESSAGE-----\\n[data]\\n-----END PGP MESSAGE-----\\n"}
+       %ExGpgme.Keys.Key{can_authenticate: false, can_certify: true,
+        can_encrypt: true, can_sign: true, chain_id: nil,
+        fingerprint: "95E93F470BCB2E96C648572DFBFA85913EE05E95",
+        has_secret: false, id: "FBFA85913EE05E95", is_disabled: false,
+        is_expired: false, is_invalid: false, is_revoked: false,
+        issuer_name: nil, issuer_serial: nil, owner_trust: :unknown,
+        protocol: :open_pgp}}
   """
-  @spec encrypt(
-          context :: context,
-          recipients :: [Key.t()],
-          data :: String.t(),
-          flags :: EncryptFlags.flags()
-        ) ::
-          {:ok, String.t()} | {:error, String.t()}
-  def encrypt(context, recipients, data, flags \\ []),
-    do: encrypt_with_flags(context, recipients, data, flags)
-
-  @spec encrypt_with_flags(
-          context :: context,
-          recipients :: [Key.t()],
-          data :: String.t(),
-          flags :: EncryptFlags.flags()
-        ) ::
-          {:ok, String.t()} | {:error, String.t()}
-  defp encrypt_with_flags(_context, _recipients, _data, _flags),
-    do: :erlang.nif_error(:nif_not_loaded)
+  @spec key_info(key :: Key.resource()) :: {:ok, Key.t()} | {:error, Error.t()}
+  def key_info(_key), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
-  See `encrypt/4`
+  See `key_info/1`.
   """
-  @spec encrypt!(
-          context :: context,
-          recipients :: [Key.t()],
-          data :: String.t(),
-          flags :: EncryptFlags.flags()
-        ) ::
-          String.t() | no_return
-  def encrypt!(context, recipients, data, flags \\ []) do
-    case encrypt(context, recipients, data, flags) do
-      {:ok, cypthertext} -> cypthertext
+  @spec key_info!(key :: Key.resource()) :: Key.t() | no_return
+  def key_info!(key) do
+    case key_info(key) do
+      {:ok, result} -> result
       {:error, error} -> raise error
     end
   end
 
   @doc """
-  Signs and encrypts a message for the specified recipients.
+  Returns the user ids of the key `key`.
 
   ### Examples
 
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
-      iex> ExGpgme.Context.set_armor(context, true)
       iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
-      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
-      iex> ExGpgme.Context.sign_and_encrypt(context, [recipient], "Hello World", [:always_trust])
-      {:ok, "-----BEGIN PGP MESSAGE-----\\n[data]\\n-----END PGP MESSAGE-----\\n"}
+      iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.user_ids(key)
+      {:ok,
+       [%ExGpgme.Keys.UserId{comment: nil, email: "sender@example.com",
+         is_invalid: false, is_revoked: false, last_update: 0, name: "Sender",
+         origin: :unknown, tofu_info: nil, uid: "Sender <sender@example.com>",
+         validity: :unknown}]}
   """
-  @spec sign_and_encrypt(
-          context :: context,
-          recipients :: [Key.t()],
-          data :: String.t(),
-          flags :: EncryptFlags.flags()
-        ) ::
-          {:ok, String.t()} | {:error, String.t()}
-  def sign_and_encrypt(context, recipients, data, flags \\ []),
-    do: sign_and_encrypt_with_flags(context, recipients, data, flags)
-
-  @spec sign_and_encrypt_with_flags(
-          context :: context,
-          recipients :: [Key.t()],
-          data :: String.t(),
-          flags :: EncryptFlags.flags()
-        ) ::
-          {:ok, String.t()} | {:error, String.t()}
-  defp sign_and_encrypt_with_flags(_context, _recipients, _data, _flags),
-    do: :erlang.nif_error(:nif_not_loaded)
+  @spec user_ids(key :: Key.resource()) :: {:ok, [UserId.t()]} | {:error, Error.t()}
+  def user_ids(_key), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
-  See `sign_and_encrypt/4`
+  See `user_ids/1`.
   """
-  @spec sign_and_encrypt!(
-          context :: context,
-          recipients :: [Key.t()],
-          data :: String.t(),
-          flags :: EncryptFlags.flags()
-        ) ::
-          String.t() | no_return
-  def sign_and_encrypt!(context, recipients, data, flags \\ []) do
-    case sign_and_encrypt(context, recipients, data, flags) do
-      {:ok, cypthertext} -> cypthertext
+  @spec user_ids!(key :: Key.resource()) :: [UserId.t()] | no_return
+  def user_ids!(key) do
+    case user_ids(key) do
+      {:ok, result} -> result
       {:error, error} -> raise error
     end
   end
 
   @doc """
-  The function deletes the key `key` from the key ring of the crypto engine used by `context`.
+  Returns the subkeys of the key `key`. The first subkey in the list is the primary key.
 
   ### Examples
 
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
       iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
       iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
-      iex> ExGpgme.Context.delete_key(context, key)
-      :ok
+      iex> ExGpgme.Context.subkeys(key)
+      {:ok,
+       [%ExGpgme.Keys.Subkey{algorithm: :rsa, can_authenticate: false,
+         can_certify: true, can_encrypt: false, can_sign: true,
+         card_serial_number: nil, creation_time: 1510124656, curve: nil,
+         expiration_time: nil, fingerprint: "95E93F470BCB2E96C648572DFBFA85913EE05E95",
+         id: "FBFA85913EE05E95", is_card_key: false, is_disabled: false,
+         is_expired: false, is_invalid: false, is_revoked: false,
+         is_secret: false, keygrip: nil, length: 4096, never_expires: true},
+        %ExGpgme.Keys.Subkey{algorithm: :rsa, can_authenticate: false,
+         can_certify: false, can_encrypt: true, can_sign: false,
+         card_serial_number: nil, creation_time: 1510124656, curve: nil,
+         expiration_time: nil, fingerprint: "AE36633EF129AE970C4CC57B0FAFFBB7D377BCE8",
+         id: "0FAFFBB7D377BCE8", is_card_key: false, is_disabled: false,
+         is_expired: false, is_invalid: false, is_revoked: false,
+         is_secret: false, keygrip: nil, length: 4096, never_expires: true}]}
   """
-  @spec delete_key(context :: context, key :: Key.t()) :: :ok | {:error, String.t()}
-  def delete_key(_context, _key), do: :erlang.nif_error(:nif_not_loaded)
+  @spec subkeys(key :: Key.resource()) :: {:ok, [Subkey.t()]} | {:error, Error.t()}
+  def subkeys(_key), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
-  See `delete_key/2`
+  See `subkeys/1`.
   """
-  @spec delete_key!(context :: context, key :: Key.t()) :: nil | no_return
-  def delete_key!(context, key) do
-    case delete_key(context, key) do
-      :ok -> nil
+  @spec subkeys!(key :: Key.resource()) :: [Subkey.t()] | no_return
+  def subkeys!(key) do
+    case subkeys(key) do
+      {:ok, result} -> result
       {:error, error} -> raise error
     end
   end
 
   @doc """
-  The function deletes the secret key `key` from the key ring of the crypto engine used by `context`.
+  Lists all keys that match one of the `patterns`. An empty list of patterns lists all keys in the key ring.
+
+  If `secret_only` is `true`, only keys for which a secret key is available are listed.
 
   ### Examples
 
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
       iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
-      iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
-      iex> ExGpgme.Context.delete_secret_key(context, key)
-      :ok
+      iex> ExGpgme.Context.list_keys(context, ["sender@example.com"])
+      {:ok, [#Reference<0.411470915.3086352388.254522>]}
   """
-  @spec delete_secret_key(context :: context, key :: Key.t()) :: :ok | {:error, String.t()}
-  def delete_secret_key(_context, _key), do: :erlang.nif_error(:nif_not_loaded)
+  @spec list_keys(context :: context, patterns :: [String.t()], secret_only :: boolean) ::
+          {:ok, [Key.resource()]} | {:error, Error.t()}
+  def list_keys(_context, _patterns \\ [], _secret_only \\ false),
+    do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
-  See `delete_secret_key/2`
+  See `list_keys/3`.
   """
-  @spec delete_secret_key!(context :: context, key :: Key.t()) :: nil | no_return
-  def delete_secret_key!(context, key) do
-    case delete_secret_key(context, key) do
-      :ok -> nil
+  @spec list_keys!(context :: context, patterns :: [String.t()], secret_only :: boolean) ::
+          [Key.resource()] | no_return
+  def list_keys!(context, patterns \\ [], secret_only \\ false) do
+    case list_keys(context, patterns, secret_only) do
+      {:ok, result} -> result
       {:error, error} -> raise error
     end
   end
 
   @doc """
-  The function decrypts the ciphertext in the argument `ciphertext` and returns the plain text.
+  Opens a cursor over all keys that match one of the `patterns`. See `list_keys/3` for the arguments.
+
+  The keys are listed on a separate thread with a copy of the context and can be fetched in batches with
+  `next_keys/2`. The listing only advances as far as the cursor is read, which keeps large key rings from being
+  loaded at once.
 
   ### Examples
 
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
-      iex> ExGpgme.Context.set_armor(context, true)
-      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
-      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
-      iex> cyphertext = ExGpgme.Context.encrypt!(context, [recipient], "Hello World", [:always_trust])
-      iex> ExGpgme.Context.decrypt(context, cyphertext)
-      {:ok, "Hello World"}
+      iex> ExGpgme.Context.open_key_cursor(context, [], false)
+      {:ok, #Reference<0.411470915.3086352388.254523>}
   """
-  @spec decrypt(context :: context, cypertext :: String.t()) ::
-          {:ok, String.t()} | {:error, String.t()}
-  def decrypt(_context, _cyphertext), do: :erlang.nif_error(:nif_not_loaded)
+  @spec open_key_cursor(context :: context, patterns :: [String.t()], secret_only :: boolean) ::
+          {:ok, key_cursor} | {:error, Error.t()}
+  def open_key_cursor(_context, _patterns \\ [], _secret_only \\ false),
+    do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
-  See `decrypt/2`
+  See `open_key_cursor/3`.
   """
-  @spec decrypt!(context :: context, cypertext :: String.t()) :: String.t() | no_return
-  def decrypt!(context, cyphertext) do
-    case decrypt(context, cyphertext) do
-      {:ok, plaintext} -> plaintext
+  @spec open_key_cursor!(context :: context, patterns :: [String.t()], secret_only :: boolean) ::
+          key_cursor | no_return
+  def open_key_cursor!(context, patterns \\ [], secret_only \\ false) do
+    case open_key_cursor(context, patterns, secret_only) do
+      {:ok, result} -> result
       {:error, error} -> raise error
     end
   end
 
   @doc """
-  The function creates a signature for the text in the `data`. The type of the signature created is determined by the
-  ASCII armor (or, if that is not set, by the encoding specified for sig), the text mode attributes set for the context
-  ctx and the requested signature mode `mode`.
+  Fetches up to `count` keys from the cursor. Returns `:done` once all keys have been read. If the listing fails, the
+  keys read before the error are returned first and the error by the next call.
 
   ### Examples
 
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
-      iex> ExGpgme.Context.set_armor(context, true)
-      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> cursor = ExGpgme.Context.open_key_cursor!(context, ["sender@example.com"])
+      iex> ExGpgme.Context.next_keys(cursor, 10)
+      {:ok, [#Reference<0.411470915.3086352388.254522>]}
+      iex> ExGpgme.Context.next_keys(cursor, 10)
+      :done
+  """
+  @spec next_keys(cursor :: key_cursor, count :: pos_integer) ::
+          {:ok, [Key.resource()]} | :done | {:error, Error.t()}
+  def next_keys(_cursor, _count), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Closes the cursor and stops the listing. Further calls to `next_keys/2` return `:done`.
+
+  ### Examples
+
+      iex> cursor = :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.open_key_cursor!
+      iex> ExGpgme.Context.close_key_cursor(cursor)
+      :ok
+  """
+  @spec close_key_cursor(cursor :: key_cursor) :: :ok
+  def close_key_cursor(_cursor), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  Streams all keys that match one of the `patterns`. The keys are fetched from a cursor in batches of `batch_size`.
+  See `open_key_cursor/3`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> context
+      ...> |> ExGpgme.Context.stream_keys(["sender@example.com"])
+      ...> |> Enum.to_list
+      [#Reference<0.411470915.3086352388.254522>]
+  """
+  @spec stream_keys(
+          context :: context,
+          patterns :: [String.t()],
+          secret_only :: boolean,
+          batch_size :: pos_integer
+        ) :: Enumerable.t()
+  def stream_keys(context, patterns \\ [], secret_only \\ false, batch_size \\ 100) do
+    Stream.resource(
+      fn -> open_key_cursor!(context, patterns, secret_only) end,
+      fn cursor ->
+        case next_keys(cursor, batch_size) do
+          {:ok, keys} -> {keys, cursor}
+          :done -> {:halt, cursor}
+          {:error, error} -> raise error
+        end
+      end,
+      &close_key_cursor/1
+    )
+  end
+
+  @doc """
+  Encrypts a message for the specified recipients. Returns the cyphertext and the `EncryptionResult`.
+
+  If the encryption fails because of unusable recipients, the error comes with the `EncryptionResult` listing the
+  `invalid_recipients`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.encrypt(context, [recipient], "Hello World", [:always_trust])
+      {:ok,
+       "-----BEGIN PGP MESSAGE-----\\n[data]\\n-----END PGP MESSAGE-----\\n",
+       %ExGpgme.Results.EncryptionResult{invalid_recipients: []}}
+  """
+  @spec encrypt(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) ::
+          {:ok, binary, EncryptionResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), EncryptionResult.t()}
+  def encrypt(context, recipients, data, flags \\ []),
+    do: encrypt_with_flags(context, recipients, data, flags)
+
+  @spec encrypt_with_flags(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) ::
+          {:ok, binary, EncryptionResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), EncryptionResult.t()}
+  defp encrypt_with_flags(_context, _recipients, _data, _flags),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `encrypt/4`
+  """
+  @spec encrypt!(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) ::
+          {binary, EncryptionResult.t()} | no_return
+  def encrypt!(context, recipients, data, flags \\ []) do
+    case encrypt(context, recipients, data, flags) do
+      {:ok, cypthertext, result} -> {cypthertext, result}
+      {:error, error} -> raise error
+      {:error, error, _result} -> raise error
+    end
+  end
+
+  @doc """
+  Signs and encrypts a message for the specified recipients. Returns the cyphertext, the `EncryptionResult` and the
+  `SigningResult`. Errors are reported like `encrypt/4`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.sign_and_encrypt(context, [recipient], "Hello World", [:always_trust])
+      {:ok, "-----BEGIN PGP MESSAGE-----\\n[data]\\n-----END PGP MESSAGE-----\\n",
+       %ExGpgme.Results.EncryptionResult{invalid_recipients: []},
+       %ExGpgme.Results.SigningResult{invalid_signers: [], new_signatures: [%ExGpgme.Results.NewSignature{...}]}}
+  """
+  @spec sign_and_encrypt(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) ::
+          {:ok, binary, EncryptionResult.t(), SigningResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), EncryptionResult.t()}
+  def sign_and_encrypt(context, recipients, data, flags \\ []),
+    do: sign_and_encrypt_with_flags(context, recipients, data, flags)
+
+  @spec sign_and_encrypt_with_flags(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) ::
+          {:ok, binary, EncryptionResult.t(), SigningResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), EncryptionResult.t()}
+  defp sign_and_encrypt_with_flags(_context, _recipients, _data, _flags),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `sign_and_encrypt/4`
+  """
+  @spec sign_and_encrypt!(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) ::
+          {binary, EncryptionResult.t(), SigningResult.t()} | no_return
+  def sign_and_encrypt!(context, recipients, data, flags \\ []) do
+    case sign_and_encrypt(context, recipients, data, flags) do
+      {:ok, cypthertext, encryption_result, signing_result} ->
+        {cypthertext, encryption_result, signing_result}
+
+      {:error, error} ->
+        raise error
+
+      {:error, error, _result} ->
+        raise error
+    end
+  end
+
+  @doc """
+  Encrypts the file at `input_path` for the specified recipients and writes the cyphertext to `output_path`.
+  The files are passed to gpgme directly and never copied into the VM. Errors are reported like `encrypt/4`.
+
+  The output is written to a temporary file in the directory of `output_path`, which replaces `output_path` only if
+  the operation succeeds. The same applies to `decrypt_file/3`, `sign_file/4` and `verify_file/3`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.encrypt_file(context, [recipient], "backup.tar", "backup.tar.gpg", [:always_trust])
+      {:ok, %ExGpgme.Results.EncryptionResult{invalid_recipients: []}}
+  """
+  @spec encrypt_file(
+          context :: context,
+          recipients :: [Key.resource()],
+          input_path :: Path.t(),
+          output_path :: Path.t(),
+          flags :: EncryptFlags.flags()
+        ) ::
+          {:ok, EncryptionResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), EncryptionResult.t()}
+  def encrypt_file(_context, _recipients, _input_path, _output_path, _flags \\ []),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `encrypt_file/5`.
+  """
+  @spec encrypt_file!(
+          context :: context,
+          recipients :: [Key.resource()],
+          input_path :: Path.t(),
+          output_path :: Path.t(),
+          flags :: EncryptFlags.flags()
+        ) :: EncryptionResult.t() | no_return
+  def encrypt_file!(context, recipients, input_path, output_path, flags \\ []) do
+    case encrypt_file(context, recipients, input_path, output_path, flags) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+      {:error, error, _result} -> raise error
+    end
+  end
+
+  @doc """
+  Decrypts the file at `input_path` and writes the plain text to `output_path`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> ExGpgme.Context.decrypt_file(context, "backup.tar.gpg", "backup.tar")
+      {:ok,
+       %ExGpgme.Results.DecryptionResult{filename: nil, recipients: [%ExGpgme.Results.Recipient{...}], ...}}
+  """
+  @spec decrypt_file(context :: context, input_path :: Path.t(), output_path :: Path.t()) ::
+          {:ok, DecryptionResult.t()} | {:error, Error.t()}
+  def decrypt_file(_context, _input_path, _output_path), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `decrypt_file/3`.
+  """
+  @spec decrypt_file!(context :: context, input_path :: Path.t(), output_path :: Path.t()) ::
+          DecryptionResult.t() | no_return
+  def decrypt_file!(context, input_path, output_path) do
+    case decrypt_file(context, input_path, output_path) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Signs the file at `input_path` and writes the signature to `output_path`. Returns the `SigningResult`.
+  See `sign/3`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> ExGpgme.Context.sign_file(context, :detached, "backup.tar", "backup.tar.sig")
+      {:ok,
+       %ExGpgme.Results.SigningResult{invalid_signers: [],
+        new_signatures: [%ExGpgme.Results.NewSignature{mode: :detached, ...}]}}
+  """
+  @spec sign_file(
+          context :: context,
+          mode :: ExGpgme.sign_mode(),
+          input_path :: Path.t(),
+          output_path :: Path.t()
+        ) ::
+          {:ok, SigningResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), SigningResult.t()}
+  def sign_file(_context, _mode \\ :normal, _input_path, _output_path),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `sign_file/4`.
+  """
+  @spec sign_file!(
+          context :: context,
+          mode :: ExGpgme.sign_mode(),
+          input_path :: Path.t(),
+          output_path :: Path.t()
+        ) :: SigningResult.t() | no_return
+  def sign_file!(context, mode \\ :normal, input_path, output_path) do
+    case sign_file(context, mode, input_path, output_path) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+      {:error, error, _result} -> raise error
+    end
+  end
+
+  @doc """
+  Verifies the signed file at `input_path`. The signed text is written to `output_path` if one is given.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> ExGpgme.Context.verify_file(context, "backup.tar.gpg", "backup.tar")
+      {:ok,
+       %ExGpgme.Results.VerificationResult{filename: nil,
+        signatures: [%ExGpgme.Results.Signature{...}]}}
+  """
+  @spec verify_file(context :: context, input_path :: Path.t(), output_path :: Path.t() | nil) ::
+          {:ok, VerificationResult.t()} | {:error, Error.t()}
+  def verify_file(_context, _input_path, _output_path \\ nil),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `verify_file/3`.
+  """
+  @spec verify_file!(context :: context, input_path :: Path.t(), output_path :: Path.t() | nil) ::
+          VerificationResult.t() | no_return
+  def verify_file!(context, input_path, output_path \\ nil) do
+    case verify_file(context, input_path, output_path) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Opens a streaming session for `operation`. The operation runs on its own thread on a copy of the context.
+
+  The input is fed in chunks with `write_session/2`, which returns the output produced so far. `finish_session/1`
+  ends the input and returns the remaining output and the result of the operation. Only a limited number of input and
+  output chunks is buffered, so `write_session/2` blocks while the operation is behind and the output never piles up
+  until `finish_session/1`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.open_session(context, {:encrypt, [recipient], [:always_trust]})
+      {:ok, #Reference<0.411470915.3086352388.254522>}
+  """
+  @spec open_session(context :: context, operation :: session_operation) ::
+          {:ok, session} | {:error, Error.t()}
+  def open_session(_context, _operation), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `open_session/2`.
+  """
+  @spec open_session!(context :: context, operation :: session_operation) :: session | no_return
+  def open_session!(context, operation) do
+    case open_session(context, operation) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Writes a chunk of input to the session and returns the output that is available. Errors of the operation are
+  reported by `finish_session/1`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> session = ExGpgme.Context.open_session!(context, {:encrypt, [recipient], [:always_trust]})
+      iex> ExGpgme.Context.write_session(session, "Hello World")
+      {:ok, ""}
+  """
+  @spec write_session(session :: session, chunk :: binary) :: {:ok, binary} | {:error, Error.t()}
+  def write_session(_session, _chunk), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `write_session/2`.
+  """
+  @spec write_session!(session :: session, chunk :: binary) :: binary | no_return
+  def write_session!(session, chunk) do
+    case write_session(session, chunk) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Ends the input of the session and waits for the operation to complete. Returns the remaining output and the result.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> session = ExGpgme.Context.open_session!(context, {:encrypt, [recipient], [:always_trust]})
+      iex> ExGpgme.Context.write_session!(session, "Hello World")
+      iex> ExGpgme.Context.finish_session(session)
+      {:ok, <<133, 2, 12, 3, ...>>, %ExGpgme.Results.EncryptionResult{invalid_recipients: []}}
+  """
+  @spec finish_session(session :: session) ::
+          {:ok, binary, session_result} | {:error, Error.t()}
+  def finish_session(_session), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `finish_session/1`.
+  """
+  @spec finish_session!(session :: session) :: {binary, session_result} | no_return
+  def finish_session!(session) do
+    case finish_session(session) do
+      {:ok, output, result} -> {output, result}
+      {:error, error} -> raise error
+    end
+  end
+
+  # libgpg-error values with gpgme as source, for the checks of `stream/3`.
+  @bad_signature 117_440_520
+  @no_data 117_440_570
+
+  @doc """
+  Runs `operation` over the chunks of `enumerable` and streams the output as the operation produces it. The operation
+  raises if it fails. See `open_session/2`.
+
+  A `:verify` stream raises an `ExGpgme.Error` at the end if the input has no signature or any signature is not
+  valid. The output is emitted before the signatures can be checked, so it must not be trusted until the stream
+  completed.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> "backup.tar"
+      ...> |> File.stream!([], 65_536)
+      ...> |> ExGpgme.Context.stream(context, {:encrypt, [recipient], [:always_trust]})
+      ...> |> Stream.into(File.stream!("backup.tar.gpg"))
+      ...> |> Stream.run()
+      :ok
+  """
+  @spec stream(
+          enumerable :: Enumerable.t(),
+          context :: context,
+          operation :: session_operation
+        ) :: Enumerable.t()
+  def stream(enumerable, context, operation) do
+    enumerable
+    |> Stream.map(&{:chunk, &1})
+    |> Stream.concat([:eof])
+    |> Stream.transform(
+      fn -> open_session!(context, operation) end,
+      fn
+        {:chunk, chunk}, session ->
+          {output_chunks(write_session!(session, chunk)), session}
+
+        :eof, session ->
+          {output, result} = finish_session!(session)
+          check_signatures!(result)
+          {output_chunks(output), session}
+      end,
+      # Finishing twice only returns an error, this closes sessions of halted streams.
+      &finish_session/1
+    )
+  end
+
+  defp output_chunks(""), do: []
+  defp output_chunks(output), do: [output]
+
+  defp check_signatures!(%VerificationResult{signatures: []}),
+    do: raise(%Error{code: :no_data, source: :gpgme, raw: @no_data, message: "No signature"})
+
+  defp check_signatures!(%VerificationResult{signatures: signatures}) do
+    unless Enum.all?(signatures, &(&1.status == :valid)) do
+      raise %Error{
+        code: :bad_signature,
+        source: :gpgme,
+        raw: @bad_signature,
+        message: "Bad signature"
+      }
+    end
+  end
+
+  defp check_signatures!(_result), do: :ok
+
+  @doc """
+  Starts encrypting `data` in the background and returns immediately. The calling process receives
+
+      {:gpgme_result, operation, {:ok, cyphertext, %EncryptionResult{}}}
+
+  or `{:gpgme_result, operation, {:error, error}}` once the operation is done. The operation runs on a copy of the
+  context taken when it starts, so the context stays usable and later changes to it do not affect the operation.
+  Taking the copy waits for any operation running on the same context in another process, and an error copying it is
+  returned right away. Background operations share a fixed number of threads, further operations wait for a free one. As the caller does
+  not block, it may also be the passphrase provider, see `set_passphrase_provider/3`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.encrypt_async(context, [recipient], "Hello World", [:always_trust])
+      #Reference<0.411470915.3086352388.254522>
+  """
+  @spec encrypt_async(
+          context :: context,
+          recipients :: [Key.resource()],
+          data :: binary,
+          flags :: EncryptFlags.flags()
+        ) :: operation | {:error, Error.t()}
+  def encrypt_async(context, recipients, data, flags \\ []),
+    do: start_operation(context, {:encrypt, recipients, flags}, data)
+
+  @doc """
+  Starts decrypting `cyphertext` in the background. The calling process receives
+  `{:gpgme_result, operation, {:ok, plaintext, %DecryptionResult{}}}` when done. See `encrypt_async/4`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/receiver_secret.asc"))
+      iex> ExGpgme.Context.decrypt_async(context, File.read!("priv/test/test_data/encrypted_receiver.asc"))
+      #Reference<0.411470915.3086352388.254522>
+  """
+  @spec decrypt_async(context :: context, cyphertext :: binary) :: operation | {:error, Error.t()}
+  def decrypt_async(context, cyphertext), do: start_operation(context, :decrypt, cyphertext)
+
+  @doc """
+  Starts signing `data` in the background. The calling process receives
+  `{:gpgme_result, operation, {:ok, signature, %SigningResult{}}}` when done. See `encrypt_async/4`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> ExGpgme.Context.sign_async(context, :clear, "Hello World")
+      #Reference<0.411470915.3086352388.254522>
+  """
+  @spec sign_async(context :: context, mode :: ExGpgme.sign_mode(), data :: binary) ::
+          operation | {:error, Error.t()}
+  def sign_async(context, mode \\ :normal, data),
+    do: start_operation(context, {:sign, mode}, data)
+
+  @doc """
+  Starts verifying the opaque `signature` in the background. The calling process receives
+  `{:gpgme_result, operation, {:ok, plaintext, %VerificationResult{}}}` when done. See `encrypt_async/4`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> ExGpgme.Context.verify_async(context, signature)
+      #Reference<0.411470915.3086352388.254522>
+  """
+  @spec verify_async(context :: context, signature :: binary) :: operation | {:error, Error.t()}
+  def verify_async(context, signature), do: start_operation(context, :verify, signature)
+
+  @doc """
+  Cancels the operation running on `context`, the background `operation`, the `session` or the listing of the
+  `key_cursor`. The canceled call returns an error with the code `:canceled` and background operations reply with it.
+  A session reports it from `finish_session/1` and a key cursor from `next_keys/2` after the keys listed before.
+
+  Operations, sessions and key cursors that accepted the cancel always end with `:canceled`, even if gpgme was about to
+  finish. Operations that did not start yet never run. Canceling them once they are done returns an error with the code
+  `:inv_state`. Canceling a context reaches synchronous operations running in other processes, but none of the
+  others, which work on a copy of the context. Canceling a context without a running operation does nothing and still
+  returns `:ok`, the next operation on it runs normally.
+
+  ### Examples
+
+      iex> :open_pgp
+      ...> |> ExGpgme.Context.from_protocol!
+      ...> |> ExGpgme.Context.cancel
+      :ok
+  """
+  @spec cancel(target :: context | operation | session | key_cursor) :: :ok | {:error, Error.t()}
+  def cancel(_target), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `cancel/1`.
+  """
+  @spec cancel!(target :: context | operation | session | key_cursor) :: nil | no_return
+  def cancel!(target) do
+    case cancel(target) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @spec start_operation(context :: context, operation :: session_operation, data :: binary) ::
+          operation | {:error, Error.t()}
+  defp start_operation(_context, _operation, _data), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  The function generates a new key with the user id `userid` and the algorithm `algorithm`. The algorithm is given in
+  the format used by `gpg --quick-gen-key`, e.g. `"rsa3072"` or `"ed25519"`. `"default"` and `"future-default"` select
+  the default algorithms of the engine, which also create an encryption subkey.
+
+  `expires` is the number of seconds from now after which the key expires. `0` selects the default expiration time of
+  the engine; use the flag `:no_expire` to create a key that does not expire.
+
+  The following flags are supported:
+
+  * `:sign`, `:encrypt`, `:cert`, `:auth` - Requests the capabilities of the key instead of the default ones.
+  * `:no_expire` - Creates a key that does not expire.
+  * `:no_password` - Creates the key without a passphrase and does not ask for one.
+  * `:force` - Creates the key even if a key with the same user id already exists.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.create_key(context, "Tenant <tenant@example.com>", "ed25519", 0, [:no_password])
+      {:ok,
+       %ExGpgme.Results.KeyGenerationResult{
+         fingerprint: "0F8A8B4B6DF5D4C5E29B1C44A6D2ED8E0D9B6C3A",
+         primary_created: true, sub_created: false, uid_created: true}}
+  """
+  @spec create_key(
+          context :: context,
+          userid :: String.t(),
+          algorithm :: String.t(),
+          expires :: non_neg_integer,
+          flags :: CreateKeyFlags.flags()
+        ) :: {:ok, KeyGenerationResult.t()} | {:error, Error.t()}
+  def create_key(_context, _userid, _algorithm \\ "default", _expires \\ 0, _flags \\ []),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `create_key/5`
+  """
+  @spec create_key!(
+          context :: context,
+          userid :: String.t(),
+          algorithm :: String.t(),
+          expires :: non_neg_integer,
+          flags :: CreateKeyFlags.flags()
+        ) :: KeyGenerationResult.t() | no_return
+  def create_key!(context, userid, algorithm \\ "default", expires \\ 0, flags \\ []) do
+    case create_key(context, userid, algorithm, expires, flags) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function adds a new subkey with the algorithm `algorithm` to the key `key`. See `create_key/5` for the
+  arguments. Without capability flags the subkey is created with the default capabilities of the algorithm.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Tenant <tenant@example.com>", "ed25519", 0, [:no_password])
+      iex> key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.create_subkey(context, key, "cv25519", 0, [:encrypt, :no_password])
+      {:ok,
+       %ExGpgme.Results.KeyGenerationResult{
+         fingerprint: "5B7F1D0D2F6A8F24D8E0D2F2C9A1B35D7E0A4C11",
+         primary_created: false, sub_created: true, uid_created: false}}
+  """
+  @spec create_subkey(
+          context :: context,
+          key :: Key.resource(),
+          algorithm :: String.t(),
+          expires :: non_neg_integer,
+          flags :: CreateKeyFlags.flags()
+        ) :: {:ok, KeyGenerationResult.t()} | {:error, Error.t()}
+  def create_subkey(_context, _key, _algorithm \\ "default", _expires \\ 0, _flags \\ []),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `create_subkey/5`
+  """
+  @spec create_subkey!(
+          context :: context,
+          key :: Key.resource(),
+          algorithm :: String.t(),
+          expires :: non_neg_integer,
+          flags :: CreateKeyFlags.flags()
+        ) :: KeyGenerationResult.t() | no_return
+  def create_subkey!(context, key, algorithm \\ "default", expires \\ 0, flags \\ []) do
+    case create_subkey(context, key, algorithm, expires, flags) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function adds the user id `userid` to the key `key`.
+
+  The key resource is not updated; use `find_key/2` to fetch the changed key.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Team <team@example.com>", "ed25519", 0, [:no_password])
+      iex> key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.add_uid(context, key, "Team <alice@example.com>")
+      :ok
+  """
+  @spec add_uid(context :: context, key :: Key.resource(), userid :: String.t()) ::
+          :ok | {:error, Error.t()}
+  def add_uid(_context, _key, _userid), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `add_uid/3`
+  """
+  @spec add_uid!(context :: context, key :: Key.resource(), userid :: String.t()) ::
+          nil | no_return
+  def add_uid!(context, key, userid) do
+    case add_uid(context, key, userid) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function revokes the user id `userid` of the key `key`. The user id must match exactly.
+
+  The key resource is not updated; use `find_key/2` to fetch the changed key.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Team <team@example.com>", "ed25519", 0, [:no_password])
+      iex> key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.add_uid!(context, key, "Team <alice@example.com>")
+      iex> ExGpgme.Context.revoke_uid(context, key, "Team <alice@example.com>")
+      :ok
+  """
+  @spec revoke_uid(context :: context, key :: Key.resource(), userid :: String.t()) ::
+          :ok | {:error, Error.t()}
+  def revoke_uid(_context, _key, _userid), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `revoke_uid/3`
+  """
+  @spec revoke_uid!(context :: context, key :: Key.resource(), userid :: String.t()) ::
+          nil | no_return
+  def revoke_uid!(context, key, userid) do
+    case revoke_uid(context, key, userid) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function sets the flag `name` of the user id `userid` of the key `key` to `value`. The user id must match
+  exactly. Currently the engine only supports the flag `"primary"`, which does not take a value. See also
+  `set_primary_uid/3`.
+
+  The key resource is not updated; use `find_key/2` to fetch the changed key.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Team <team@example.com>", "ed25519", 0, [:no_password])
+      iex> key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.add_uid!(context, key, "Team <alice@example.com>")
+      iex> ExGpgme.Context.set_uid_flag(context, key, "Team <alice@example.com>", "primary")
+      :ok
+  """
+  @spec set_uid_flag(
+          context :: context,
+          key :: Key.resource(),
+          userid :: String.t(),
+          name :: String.t(),
+          value :: String.t() | nil
+        ) :: :ok | {:error, Error.t()}
+  def set_uid_flag(_context, _key, _userid, _name, _value \\ nil),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `set_uid_flag/5`
+  """
+  @spec set_uid_flag!(
+          context :: context,
+          key :: Key.resource(),
+          userid :: String.t(),
+          name :: String.t(),
+          value :: String.t() | nil
+        ) :: nil | no_return
+  def set_uid_flag!(context, key, userid, name, value \\ nil) do
+    case set_uid_flag(context, key, userid, name, value) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function marks the user id `userid` as the primary user id of the key `key`. See `set_uid_flag/5`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Team <team@example.com>", "ed25519", 0, [:no_password])
+      iex> key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.add_uid!(context, key, "Team <alice@example.com>")
+      iex> ExGpgme.Context.set_primary_uid(context, key, "Team <alice@example.com>")
+      :ok
+  """
+  @spec set_primary_uid(context :: context, key :: Key.resource(), userid :: String.t()) ::
+          :ok | {:error, Error.t()}
+  def set_primary_uid(context, key, userid), do: set_uid_flag(context, key, userid, "primary")
+
+  @doc """
+  See `set_primary_uid/3`
+  """
+  @spec set_primary_uid!(context :: context, key :: Key.resource(), userid :: String.t()) ::
+          nil | no_return
+  def set_primary_uid!(context, key, userid) do
+    case set_primary_uid(context, key, userid) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function sets the expiration time of the key `key`. `expires` is either `:never`, a number of seconds from now,
+  or an absolute point in time. `subkeys` selects whether the expiration of the primary key (`:primary`), of all
+  subkeys (`:all`) or of the subkeys with the given fingerprints is changed.
+
+  The key resource is not updated; use `find_key/2` to fetch the changed key.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Team <team@example.com>", "ed25519", 0, [:no_password])
+      iex> key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.set_expire(context, key, 365 * 24 * 60 * 60)
+      :ok
+      iex> ExGpgme.Context.set_expire(context, key, ~U[2040-01-01 00:00:00Z], :all)
+      :ok
+  """
+  @spec set_expire(
+          context :: context,
+          key :: Key.resource(),
+          expires :: Keys.expiration(),
+          subkeys :: Keys.expiration_target()
+        ) :: :ok | {:error, Error.t()}
+  def set_expire(context, key, expires, subkeys \\ :primary)
+
+  def set_expire(context, key, %DateTime{} = expires, subkeys),
+    do: set_key_expire(context, key, {:at, DateTime.to_unix(expires)}, subkeys)
+
+  def set_expire(context, key, expires, subkeys),
+    do: set_key_expire(context, key, expires, subkeys)
+
+  @spec set_key_expire(
+          context :: context,
+          key :: Key.resource(),
+          expires :: Keys.expiration(),
+          subkeys :: Keys.expiration_target()
+        ) :: :ok | {:error, Error.t()}
+  defp set_key_expire(_context, _key, _expires, _subkeys), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `set_expire/4`
+  """
+  @spec set_expire!(
+          context :: context,
+          key :: Key.resource(),
+          expires :: Keys.expiration(),
+          subkeys :: Keys.expiration_target()
+        ) :: nil | no_return
+  def set_expire!(context, key, expires, subkeys \\ :primary) do
+    case set_expire(context, key, expires, subkeys) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function certifies the user ids `userids` of the key `key` with the signing keys of the context. If no signing
+  key has been selected, the default key of the engine is used. An empty list of user ids certifies all user ids of
+  the key.
+
+  `expires` is the number of seconds from now after which the certification expires. `0` selects the default of the
+  engine.
+
+  The following flags are supported:
+
+  * `:local` - Creates a local, non-exportable certification.
+  * `:no_expire` - Creates a certification that does not expire.
+  * `:force` - Creates the certification even if the user id has already been certified.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.create_key!(context, "Signer <signer@example.com>", "ed25519", 0, [:no_password])
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/receiver_public.asc"))
+      iex> key = ExGpgme.Context.find_key!(context, "9D8A23BADCFA63B58B3B1CED391062831D088C71")
+      iex> ExGpgme.Context.sign_key(context, key, ["Receiver <receiver@example.com>"], 0, [:local])
+      :ok
+  """
+  @spec sign_key(
+          context :: context,
+          key :: Key.resource(),
+          userids :: [String.t()],
+          expires :: non_neg_integer,
+          flags :: KeySigningFlags.flags()
+        ) :: :ok | {:error, Error.t()}
+  def sign_key(_context, _key, _userids \\ [], _expires \\ 0, _flags \\ []),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `sign_key/5`
+  """
+  @spec sign_key!(
+          context :: context,
+          key :: Key.resource(),
+          userids :: [String.t()],
+          expires :: non_neg_integer,
+          flags :: KeySigningFlags.flags()
+        ) :: nil | no_return
+  def sign_key!(context, key, userids \\ [], expires \\ 0, flags \\ []) do
+    case sign_key(context, key, userids, expires, flags) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function revokes the certifications made by `signing_key` on the user ids `userids` of the key `key`. An empty
+  list of user ids revokes the certifications on all user ids of the key.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> %{fingerprint: fingerprint} =
+      ...>   ExGpgme.Context.create_key!(context, "Signer <signer@example.com>", "ed25519", 0, [:no_password])
+      iex> signing_key = ExGpgme.Context.find_key!(context, fingerprint)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/receiver_public.asc"))
+      iex> key = ExGpgme.Context.find_key!(context, "9D8A23BADCFA63B58B3B1CED391062831D088C71")
+      iex> ExGpgme.Context.sign_key!(context, key)
+      iex> ExGpgme.Context.revoke_signature(context, key, signing_key)
+      :ok
+  """
+  @spec revoke_signature(
+          context :: context,
+          key :: Key.resource(),
+          signing_key :: Key.resource(),
+          userids :: [String.t()]
+        ) :: :ok | {:error, Error.t()}
+  def revoke_signature(_context, _key, _signing_key, _userids \\ []),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `revoke_signature/4`
+  """
+  @spec revoke_signature!(
+          context :: context,
+          key :: Key.resource(),
+          signing_key :: Key.resource(),
+          userids :: [String.t()]
+        ) :: nil | no_return
+  def revoke_signature!(context, key, signing_key, userids \\ []) do
+    case revoke_signature(context, key, signing_key, userids) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function deletes the key `key` from the key ring of the crypto engine used by `context`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.delete_key(context, key)
+      :ok
+  """
+  @spec delete_key(context :: context, key :: Key.resource()) :: :ok | {:error, Error.t()}
+  def delete_key(_context, _key), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `delete_key/2`
+  """
+  @spec delete_key!(context :: context, key :: Key.resource()) :: nil | no_return
+  def delete_key!(context, key) do
+    case delete_key(context, key) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function deletes the secret key `key` from the key ring of the crypto engine used by `context`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_public.asc"))
+      iex> key = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> ExGpgme.Context.delete_secret_key(context, key)
+      :ok
+  """
+  @spec delete_secret_key(context :: context, key :: Key.resource()) :: :ok | {:error, Error.t()}
+  def delete_secret_key(_context, _key), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `delete_secret_key/2`
+  """
+  @spec delete_secret_key!(context :: context, key :: Key.resource()) :: nil | no_return
+  def delete_secret_key!(context, key) do
+    case delete_secret_key(context, key) do
+      :ok -> nil
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function decrypts the ciphertext in the argument `ciphertext` and returns the plain text together with the
+  `DecryptionResult`. The recipients of the result tell which key was used to decrypt the message.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> {cyphertext, _} = ExGpgme.Context.encrypt!(context, [recipient], "Hello World", [:always_trust])
+      iex> ExGpgme.Context.decrypt(context, cyphertext)
+      {:ok, "Hello World",
+       %ExGpgme.Results.DecryptionResult{filename: nil, is_de_vs: false, is_mime: false,
+        is_wrong_key_usage: false, legacy_cipher_no_mdc: false,
+        recipients: [%ExGpgme.Results.Recipient{algorithm: :rsa, key_id: "0FAFFBB7D377BCE8", status: :ok}],
+        session_key: nil, symmetric_key_algorithm: "AES256.CFB", unsupported_algorithm: nil}}
+  """
+  @spec decrypt(context :: context, cypertext :: binary) ::
+          {:ok, binary, DecryptionResult.t()} | {:error, Error.t()}
+  def decrypt(_context, _cyphertext), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `decrypt/2`
+  """
+  @spec decrypt!(context :: context, cypertext :: binary) ::
+          {binary, DecryptionResult.t()} | no_return
+  def decrypt!(context, cyphertext) do
+    case decrypt(context, cyphertext) do
+      {:ok, plaintext, result} -> {plaintext, result}
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Decrypts the ciphertext like `decrypt/2` with the decryption `flags`.
+
+  * `:verify` - Also verify signatures of the message. Use `decrypt_and_verify/2` to get the `VerificationResult`.
+  * `:unwrap` - Remove only the encryption layer and return the inner OpenPGP message.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> {cyphertext, _, _} = ExGpgme.Context.sign_and_encrypt!(context, [recipient], "Hello World", [:always_trust])
+      iex> ExGpgme.Context.decrypt_with_flags(context, cyphertext, [:unwrap])
+      {:ok, <<163, 1, 0, 69, ...>>, %ExGpgme.Results.DecryptionResult{...}}
+  """
+  @spec decrypt_with_flags(
+          context :: context,
+          cypertext :: binary,
+          flags :: DecryptFlags.flags()
+        ) :: {:ok, binary, DecryptionResult.t()} | {:error, Error.t()}
+  def decrypt_with_flags(_context, _cyphertext, _flags), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `decrypt_with_flags/3`
+  """
+  @spec decrypt_with_flags!(
+          context :: context,
+          cypertext :: binary,
+          flags :: DecryptFlags.flags()
+        ) :: {binary, DecryptionResult.t()} | no_return
+  def decrypt_with_flags!(context, cyphertext, flags) do
+    case decrypt_with_flags(context, cyphertext, flags) do
+      {:ok, plaintext, result} -> {plaintext, result}
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  Decrypts the ciphertext and verifies the signatures of a signed and encrypted message. Returns the plain text, the
+  `DecryptionResult` and the `VerificationResult`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> recipient = ExGpgme.Context.find_key!(context, "95E93F470BCB2E96C648572DFBFA85913EE05E95")
+      iex> {cyphertext, _, _} = ExGpgme.Context.sign_and_encrypt!(context, [recipient], "Hello World", [:always_trust])
+      iex> ExGpgme.Context.decrypt_and_verify(context, cyphertext)
+      {:ok, "Hello World",
+       %ExGpgme.Results.DecryptionResult{...},
+       %ExGpgme.Results.VerificationResult{filename: nil, signatures: [%ExGpgme.Results.Signature{...}]}}
+  """
+  @spec decrypt_and_verify(context :: context, cypertext :: binary) ::
+          {:ok, binary, DecryptionResult.t(), VerificationResult.t()} | {:error, Error.t()}
+  def decrypt_and_verify(_context, _cyphertext), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `decrypt_and_verify/2`
+  """
+  @spec decrypt_and_verify!(context :: context, cypertext :: binary) ::
+          {binary, DecryptionResult.t(), VerificationResult.t()} | no_return
+  def decrypt_and_verify!(context, cyphertext) do
+    case decrypt_and_verify(context, cyphertext) do
+      {:ok, plaintext, decryption_result, verification_result} ->
+        {plaintext, decryption_result, verification_result}
+
+      {:error, error} ->
+        raise error
+    end
+  end
+
+  @doc """
+  The function creates a signature for the text in the `data`. The type of the signature created is determined by the
+  ASCII armor (or, if that is not set, by the encoding specified for sig), the text mode attributes set for the context
+  ctx and the requested signature mode `mode`.
+
+  Returns the signature and the `SigningResult` describing the key and digest of every created signature. If signing
+  fails because of unusable signers, the error comes with the `SigningResult` listing the `invalid_signers`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
       iex> ExGpgme.Context.sign(context, "Hello World")
-      {:ok, "-----BEGIN PGP MESSAGE-----\\n[data]\\n-----END PGP MESSAGE-----\\n"}
+      {:ok, "-----BEGIN PGP MESSAGE-----\\n[data]\\n-----END PGP MESSAGE-----\\n",
+       %ExGpgme.Results.SigningResult{invalid_signers: [],
+        new_signatures: [%ExGpgme.Results.NewSignature{creation_time: 1510648065,
+          fingerprint: "95E93F470BCB2E96C648572DFBFA85913EE05E95",
+          hash_algorithm: :sha256, key_algorithm: :rsa, mode: :normal,
+          signature_class: 0}]}}
   """
-  @spec sign(context :: context, mode :: ExGpgme.sign_mode(), data :: String.t()) ::
-          {:ok, String.t()} | {:error, String.t()}
+  @spec sign(context :: context, mode :: ExGpgme.sign_mode(), data :: binary) ::
+          {:ok, binary, SigningResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), SigningResult.t()}
   def sign(context, mode \\ :normal, data), do: sign_with_mode(context, mode, data)
 
-  @spec sign_with_mode(context :: context, mode :: ExGpgme.sign_mode(), data :: String.t()) ::
-          {:ok, String.t()} | {:error, String.t()}
+  @spec sign_with_mode(context :: context, mode :: ExGpgme.sign_mode(), data :: binary) ::
+          {:ok, binary, SigningResult.t()}
+          | {:error, Error.t()}
+          | {:error, Error.t(), SigningResult.t()}
   defp sign_with_mode(_context, _mode, _data), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
   See `sign/3`
   """
-  @spec sign!(context :: context, mode :: ExGpgme.sign_mode(), data :: String.t()) ::
-          String.t() | no_return
+  @spec sign!(context :: context, mode :: ExGpgme.sign_mode(), data :: binary) ::
+          {binary, SigningResult.t()} | no_return
   def sign!(context, mode \\ :normal, data) do
     case sign(context, mode, data) do
-      {:ok, signature} -> signature
+      {:ok, signature, result} -> {signature, result}
       {:error, error} -> raise error
+      {:error, error, _result} -> raise error
     end
   end
 
@@ -634,7 +2079,7 @@ defmodule ExGpgme.Context do
       iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
       iex> ExGpgme.Context.set_armor(context, true)
       iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
-      iex> signature = ExGpgme.Context.sign!(context, "Hello World")
+      iex> {signature, _} = ExGpgme.Context.sign!(context, "Hello World")
       iex> ExGpgme.Context.verify_opaque(context, signature, "Hello World")
       {:ok,
         %ExGpgme.Results.VerificationResult{filename: nil,
@@ -647,14 +2092,14 @@ defmodule ExGpgme.Context do
            pka_trust: :unknown, policy_url: nil, status: :valid,
            validity: :full, verified_by_chain: false}]}}
   """
-  @spec verify_opaque(context :: context, signature :: String.t(), data :: String.t()) ::
-          {:ok, VerificationResult.t()} | {:error, String.t()}
+  @spec verify_opaque(context :: context, signature :: binary, data :: binary) ::
+          {:ok, VerificationResult.t()} | {:error, Error.t()}
   def verify_opaque(_context, _signature, _data), do: :erlang.nif_error(:nif_not_loaded)
 
   @doc """
   See `verify_opaque/3`
   """
-  @spec verify_opaque!(context :: context, signature :: String.t(), data :: String.t()) ::
+  @spec verify_opaque!(context :: context, signature :: binary, data :: binary) ::
           VerificationResult.t() | no_return
   def verify_opaque!(context, signature, data) do
     case verify_opaque(context, signature, data) do
@@ -662,4 +2107,65 @@ defmodule ExGpgme.Context do
       {:error, error} -> raise error
     end
   end
+
+  @doc """
+  The function verifies the detached signature `signature` over the signed `data`. Detached signatures are created
+  with the `:detached` mode of `sign/3`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.set_armor(context, true)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> {signature, _} = ExGpgme.Context.sign!(context, :detached, "Hello World")
+      iex> ExGpgme.Context.verify_detached(context, signature, "Hello World")
+      {:ok,
+        %ExGpgme.Results.VerificationResult{filename: nil,
+         signatures: [%ExGpgme.Results.Signature{status: :valid, ...}]}}
+  """
+  @spec verify_detached(context :: context, signature :: binary, data :: binary) ::
+          {:ok, VerificationResult.t()} | {:error, Error.t()}
+  def verify_detached(_context, _signature, _data), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `verify_detached/3`
+  """
+  @spec verify_detached!(context :: context, signature :: binary, data :: binary) ::
+          VerificationResult.t() | no_return
+  def verify_detached!(context, signature, data) do
+    case verify_detached(context, signature, data) do
+      {:ok, result} -> result
+      {:error, error} -> raise error
+    end
+  end
+
+  @doc """
+  The function verifies the cleartext signature `signature` and returns the signed text together with the
+  `VerificationResult`. Cleartext signatures are created with the `:clear` mode of `sign/3`.
+
+  ### Examples
+
+      iex> context = ExGpgme.Context.from_protocol!(:open_pgp)
+      iex> ExGpgme.Context.import!(context, File.read!("priv/test/keys/sender_secret.asc"))
+      iex> {signature, _} = ExGpgme.Context.sign!(context, :clear, "Hello World")
+      iex> ExGpgme.Context.verify_clear(context, signature)
+      {:ok, "Hello World\\n",
+        %ExGpgme.Results.VerificationResult{filename: nil,
+         signatures: [%ExGpgme.Results.Signature{status: :valid, ...}]}}
+  """
+  @spec verify_clear(context :: context, signature :: binary) ::
+          {:ok, binary, VerificationResult.t()} | {:error, Error.t()}
+  def verify_clear(_context, _signature), do: :erlang.nif_error(:nif_not_loaded)
+
+  @doc """
+  See `verify_clear/2`
+  """
+  @spec verify_clear!(context :: context, signature :: binary) ::
+          {binary, VerificationResult.t()} | no_return
+  def verify_clear!(context, signature) do
+    case verify_clear(context, signature) do
+      {:ok, plaintext, result} -> {plaintext, result}
+      {:error, error} -> raise error
+    end
+  end
 end
@@ -0,0 +1,22 @@
+defmodule ExGpgme.CreateKeyFlags do
+  @moduledoc """
+  Holds key creation flags
+  """
+
+  @typedoc """
+  Flags for key creation functions
+  """
+  @type flag ::
+          :sign
+          | :encrypt
+          | :cert
+          | :auth
+          | :no_expire
+          | :no_password
+          | :force
+
+  @typedoc """
+  List of flags
+  """
+  @type flags :: list(flag)
+end
@@ -0,0 +1,17 @@
+defmodule ExGpgme.DecryptFlags do
+  @moduledoc """
+  Holds decryption flags
+  """
+
+  @typedoc """
+  Flags for decryption functions
+  """
+  @type flag ::
+          :verify
+          | :unwrap
+
+  @typedoc """
+  List of flags
+  """
+  @type flags :: list(flag)
+end
@@ -0,0 +1,60 @@
+defmodule ExGpgme.Error do
+  @moduledoc """
+  Error returned by gpgme
+
+  `code` is an atom for the common error codes and `:other` for everything else, `raw` always contains the full
+  libgpg-error value. `source` is the component that reported the error.
+
+  ### Examples
+
+      case ExGpgme.Context.find_key(context, fingerprint) do
+        {:ok, key} -> key
+        {:error, %ExGpgme.Error{code: :eof}} -> nil
+      end
+  """
+
+  defexception [:code, :source, :raw, :message]
+
+  @type code ::
+          :no_data
+          | :bad_passphrase
+          | :no_pubkey
+          | :no_seckey
+          | :canceled
+          | :unusable_pubkey
+          | :unusable_seckey
+          | :bad_signature
+          | :eof
+          | :not_found
+          | :inv_value
+          | :inv_name
+          | :unknown_name
+          | :ambiguous_name
+          | :timeout
+          | :general
+          | :bad_data
+          | :inv_state
+          | :not_supported
+          | :other
+
+  @type source ::
+          :unknown
+          | :gcrypt
+          | :gpg
+          | :gpgsm
+          | :gpg_agent
+          | :pinentry
+          | :scd
+          | :gpgme
+          | :keybox
+          | :ksba
+          | :dirmngr
+          | :other
+
+  @type t :: %__MODULE__{
+          code: code,
+          source: source,
+          raw: non_neg_integer,
+          message: String.t()
+        }
+end
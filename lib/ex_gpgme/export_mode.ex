@@ -0,0 +1,28 @@
+defmodule ExGpgme.ExportMode do
+  @moduledoc """
+  Holds export modes
+  """
+
+  @typedoc """
+  Modes for key export functions
+
+  * `:extern` - Send the keys to the keyserver instead of returning them
+  * `:minimal` - Strip all signatures except the most recent self-signatures
+  * `:secret` - Export the secret keys instead of the public keys
+  * `:raw` - Export secret keys in raw format (X.509 only)
+  * `:pkcs12` - Export secret keys in PKCS#12 format (X.509 only)
+  * `:ssh` - Export a public key in the format used by `ssh`
+  """
+  @type mode ::
+          :extern
+          | :minimal
+          | :secret
+          | :raw
+          | :pkcs12
+          | :ssh
+
+  @typedoc """
+  List of modes
+  """
+  @type modes :: list(mode)
+end
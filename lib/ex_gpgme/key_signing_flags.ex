@@ -0,0 +1,18 @@
+defmodule ExGpgme.KeySigningFlags do
+  @moduledoc """
+  Holds key signing flags
+  """
+
+  @typedoc """
+  Flags for key signing functions
+  """
+  @type flag ::
+          :local
+          | :no_expire
+          | :force
+
+  @typedoc """
+  List of flags
+  """
+  @type flags :: list(flag)
+end
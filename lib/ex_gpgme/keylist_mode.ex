@@ -0,0 +1,27 @@
+defmodule ExGpgme.KeylistMode do
+  @moduledoc """
+  Holds keylist modes
+  """
+
+  @typedoc """
+  Keylist mode
+
+  `:locate` is a shorthand for `:local` and `:extern` and is therefore never returned by
+  `ExGpgme.Context.keylist_mode/1`.
+  """
+  @type mode ::
+          :local
+          | :extern
+          | :sigs
+          | :sig_notations
+          | :with_secret
+          | :with_tofu
+          | :ephemeral
+          | :validate
+          | :locate
+
+  @typedoc """
+  List of modes
+  """
+  @type modes :: list(mode)
+end
@@ -0,0 +1,48 @@
+defmodule ExGpgme.Keys do
+  @moduledoc """
+  Keys Structs
+  """
+
+  @typedoc """
+  Origin of a key or user id
+  """
+  @type key_origin ::
+          :unknown
+          | :key_server
+          | :dane
+          | :wkd
+          | :url
+          | :file
+          | :self
+          | {:other, integer}
+
+  @typedoc """
+  Expiration of a key
+
+  * `:never` - The key does not expire.
+  * `seconds` - The key expires the given number of seconds from now.
+  * `DateTime` or `{:at, unix_timestamp}` - The key expires at the given point in time, which must be in the future.
+  """
+  @type expiration :: :never | non_neg_integer | DateTime.t() | {:at, non_neg_integer}
+
+  @typedoc """
+  Subkeys whose expiration is changed
+
+  * `:primary` - Only the primary key.
+  * `:all` - All subkeys, but not the primary key.
+  * list of fingerprints - The subkeys with the given fingerprints.
+  """
+  @type expiration_target :: :primary | :all | [String.t()]
+
+  @typedoc """
+  TOFU Policy
+  """
+  @type tofu_policy ::
+          :none
+          | :auto
+          | :good
+          | :unknown
+          | :bad
+          | :ask
+          | {:other, integer}
+end
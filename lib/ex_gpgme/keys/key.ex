@@ -6,5 +6,44 @@ defmodule ExGpgme.Keys.Key do
   @typedoc """
   Holds a key.
   """
-  @opaque t :: reference
+  @opaque resource :: reference
+
+  @enforce_keys [
+    :fingerprint,
+    :id,
+    :protocol,
+    :owner_trust,
+    :is_revoked,
+    :is_expired,
+    :is_disabled,
+    :is_invalid,
+    :can_encrypt,
+    :can_sign,
+    :can_certify,
+    :can_authenticate,
+    :has_secret,
+    :issuer_serial,
+    :issuer_name,
+    :chain_id
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          fingerprint: String.t() | nil,
+          id: String.t() | nil,
+          protocol: ExGpgme.protocol(),
+          owner_trust: ExGpgme.validity(),
+          is_revoked: boolean,
+          is_expired: boolean,
+          is_disabled: boolean,
+          is_invalid: boolean,
+          can_encrypt: boolean,
+          can_sign: boolean,
+          can_certify: boolean,
+          can_authenticate: boolean,
+          has_secret: boolean,
+          issuer_serial: String.t() | nil,
+          issuer_name: String.t() | nil,
+          chain_id: String.t() | nil
+        }
 end
@@ -0,0 +1,52 @@
+defmodule ExGpgme.Keys.Subkey do
+  @moduledoc """
+  Subkey of a key. The first subkey is the primary key.
+  """
+
+  @enforce_keys [
+    :id,
+    :fingerprint,
+    :algorithm,
+    :length,
+    :curve,
+    :creation_time,
+    :expiration_time,
+    :never_expires,
+    :keygrip,
+    :card_serial_number,
+    :is_card_key,
+    :is_secret,
+    :is_revoked,
+    :is_expired,
+    :is_disabled,
+    :is_invalid,
+    :can_encrypt,
+    :can_sign,
+    :can_certify,
+    :can_authenticate
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          id: String.t() | nil,
+          fingerprint: String.t() | nil,
+          algorithm: ExGpgme.key_algorithm(),
+          length: non_neg_integer,
+          curve: String.t() | nil,
+          creation_time: non_neg_integer | nil,
+          expiration_time: non_neg_integer | nil,
+          never_expires: boolean,
+          keygrip: String.t() | nil,
+          card_serial_number: String.t() | nil,
+          is_card_key: boolean,
+          is_secret: boolean,
+          is_revoked: boolean,
+          is_expired: boolean,
+          is_disabled: boolean,
+          is_invalid: boolean,
+          can_encrypt: boolean,
+          can_sign: boolean,
+          can_certify: boolean,
+          can_authenticate: boolean
+        }
+end
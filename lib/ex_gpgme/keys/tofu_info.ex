@@ -0,0 +1,32 @@
+defmodule ExGpgme.Keys.TofuInfo do
+  @moduledoc """
+  TOFU statistics of a user id.
+  """
+
+  alias ExGpgme.Keys
+
+  @enforce_keys [
+    :validity,
+    :policy,
+    :signature_count,
+    :encrypted_count,
+    :first_signed,
+    :last_signed,
+    :first_encrypted,
+    :last_encrypted,
+    :description
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          validity: non_neg_integer,
+          policy: Keys.tofu_policy(),
+          signature_count: non_neg_integer,
+          encrypted_count: non_neg_integer,
+          first_signed: non_neg_integer | nil,
+          last_signed: non_neg_integer | nil,
+          first_encrypted: non_neg_integer | nil,
+          last_encrypted: non_neg_integer | nil,
+          description: String.t() | nil
+        }
+end
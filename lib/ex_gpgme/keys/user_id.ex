@@ -0,0 +1,35 @@
+defmodule ExGpgme.Keys.UserId do
+  @moduledoc """
+  User ID of a key
+  """
+
+  alias ExGpgme.Keys
+  alias ExGpgme.Keys.TofuInfo
+
+  @enforce_keys [
+    :uid,
+    :name,
+    :email,
+    :comment,
+    :validity,
+    :is_revoked,
+    :is_invalid,
+    :origin,
+    :last_update,
+    :tofu_info
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          uid: String.t() | nil,
+          name: String.t() | nil,
+          email: String.t() | nil,
+          comment: String.t() | nil,
+          validity: ExGpgme.validity(),
+          is_revoked: boolean,
+          is_invalid: boolean,
+          origin: Keys.key_origin(),
+          last_update: non_neg_integer,
+          tofu_info: TofuInfo.t() | nil
+        }
+end
@@ -0,0 +1,209 @@
+defmodule ExGpgme.Pool do
+  @moduledoc """
+  Pool of contexts
+
+  Calls on one context run one after another. The pool creates `size` contexts with the same protocol, home directory
+  and pinentry mode up front and lends them out per operation, so independent operations use all cores. Use one pool
+  per home directory to keep tenants apart.
+
+  On check-in the pool replaces the context with a new one, so nothing set by the borrower like signers, flags, the
+  home directory or handlers is carried over to the next checkout. The new context is created outside of the pool
+  process and retried with a growing delay if that fails. Contexts of processes that exit while holding them are
+  checked in automatically.
+
+  ### Examples
+
+      children = [
+        {ExGpgme.Pool, name: MyApp.GpgPool, size: 4, home_dir: "/var/lib/my_app/gnupg"}
+      ]
+
+      ExGpgme.Pool.checkout(MyApp.GpgPool, fn context ->
+        ExGpgme.Context.decrypt(context, cyphertext)
+      end)
+  """
+
+  use GenServer
+
+  alias ExGpgme.Context
+
+  @min_retry_delay 100
+  @max_retry_delay 10_000
+
+  @typedoc """
+  * `:size` - Number of contexts, defaults to the number of schedulers
+  * `:protocol` - Protocol of the contexts, defaults to `:open_pgp`
+  * `:home_dir` - Home directory of the engine, see `ExGpgme.Context.set_engine_home_dir/2`
+  * `:pinentry_mode` - Pinentry mode of the contexts, see `ExGpgme.Context.set_pinentry_mode/2`
+  * `:configure` - Function called with every new context after the other options are applied
+  * `:name` - Name to register the pool under
+  """
+  @type option ::
+          {:size, pos_integer}
+          | {:protocol, ExGpgme.protocol()}
+          | {:home_dir, String.t()}
+          | {:pinentry_mode, ExGpgme.pinentry_mode()}
+          | {:configure, (Context.context() -> any)}
+          | {:name, GenServer.name()}
+
+  @doc """
+  Starts a pool with the given options. See `t:option/0`.
+  """
+  @spec start_link(opts :: [option]) :: GenServer.on_start()
+  def start_link(opts \\ []) do
+    {server_opts, pool_opts} = Keyword.split(opts, [:name])
+
+    case Keyword.get(pool_opts, :size) do
+      nil -> :ok
+      size when is_integer(size) and size >= 1 -> :ok
+      size ->
+        raise ArgumentError, "expected :size to be a positive integer, got: #{inspect(size)}"
+    end
+
+    GenServer.start_link(__MODULE__, pool_opts, server_opts)
+  end
+
+  @doc """
+  Checks out a context, calls `fun` with it and checks the context back in. Returns the result of `fun`.
+
+  Waits up to `timeout` milliseconds for a free context and exits like `GenServer.call/3` if none gets available.
+  Background operations started with the context have to be done before `fun` returns.
+  """
+  @spec checkout(pool :: GenServer.server(), fun :: (Context.context() -> result), timeout) ::
+          result
+        when result: var
+  def checkout(pool, fun, timeout \\ 5_000) do
+    ref = make_ref()
+
+    context =
+      try do
+        GenServer.call(pool, {:checkout, ref}, timeout)
+      catch
+        :exit, reason ->
+          GenServer.cast(pool, {:checkin, ref})
+          exit(reason)
+      end
+
+    try do
+      fun.(context)
+    after
+      GenServer.cast(pool, {:checkin, ref})
+    end
+  end
+
+  @impl GenServer
+  def init(opts) do
+    size = Keyword.get(opts, :size, System.schedulers_online())
+
+    settings = %{
+      protocol: Keyword.get(opts, :protocol, :open_pgp),
+      home_dir: opts[:home_dir],
+      pinentry_mode: opts[:pinentry_mode],
+      configure: opts[:configure]
+    }
+
+    contexts = for _ <- 1..size, do: new_context(settings)
+    {:ok, tasks} = Task.Supervisor.start_link()
+
+    {:ok,
+     %{
+       idle: contexts,
+       waiting: :queue.new(),
+       clients: %{},
+       leased: %{},
+       replacing: %{},
+       tasks: tasks,
+       settings: settings
+     }}
+  end
+
+  @impl GenServer
+  def handle_call({:checkout, ref}, {pid, _tag} = from, state) do
+    state = put_in(state.clients[ref], Process.monitor(pid))
+
+    case state.idle do
+      [context | idle] ->
+        {:reply, context, %{state | idle: idle, leased: Map.put(state.leased, ref, context)}}
+
+      [] ->
+        {:noreply, %{state | waiting: :queue.in({ref, from}, state.waiting)}}
+    end
+  end
+
+  @impl GenServer
+  def handle_cast({:checkin, ref}, state), do: {:noreply, checkin(state, ref)}
+
+  @impl GenServer
+  def handle_info({ref, context}, %{replacing: replacing} = state)
+      when is_map_key(replacing, ref) do
+    Process.demonitor(ref, [:flush])
+
+    {:noreply, lend(%{state | replacing: Map.delete(replacing, ref)}, context)}
+  end
+
+  def handle_info({:DOWN, ref, :process, _pid, _reason}, %{replacing: replacing} = state)
+      when is_map_key(replacing, ref) do
+    {delay, replacing} = Map.pop(replacing, ref)
+    Process.send_after(self(), {:replace, min(delay * 2, @max_retry_delay)}, delay)
+
+    {:noreply, %{state | replacing: replacing}}
+  end
+
+  def handle_info({:replace, delay}, state), do: {:noreply, replace(state, delay)}
+
+  def handle_info({:DOWN, monitor, :process, _pid, _reason}, state) do
+    case Enum.find(state.clients, fn {_ref, client_monitor} -> client_monitor == monitor end) do
+      {ref, _monitor} -> {:noreply, checkin(state, ref)}
+      nil -> {:noreply, state}
+    end
+  end
+
+  defp checkin(state, ref) do
+    {monitor, clients} = Map.pop(state.clients, ref)
+    if monitor, do: Process.demonitor(monitor, [:flush])
+
+    state = %{state | clients: clients}
+
+    # Waiting clients that gave up are removed from `clients` and skipped by `lend/2`.
+    case Map.pop(state.leased, ref) do
+      {nil, _leased} ->
+        state
+
+      {_context, leased} ->
+        replace(%{state | leased: leased}, @min_retry_delay)
+    end
+  end
+
+  # Creating a context calls into gpgme, which must not hold up the pool.
+  # `delay` is the wait before the next attempt if this one fails.
+  defp replace(state, delay) do
+    settings = state.settings
+    task = Task.Supervisor.async_nolink(state.tasks, fn -> new_context(settings) end)
+
+    put_in(state.replacing[task.ref], delay)
+  end
+
+  defp lend(state, context) do
+    case :queue.out(state.waiting) do
+      {{:value, {ref, from}}, waiting} ->
+        if Map.has_key?(state.clients, ref) do
+          GenServer.reply(from, context)
+          %{state | waiting: waiting, leased: Map.put(state.leased, ref, context)}
+        else
+          lend(%{state | waiting: waiting}, context)
+        end
+
+      {:empty, _waiting} ->
+        %{state | idle: [context | state.idle]}
+    end
+  end
+
+  defp new_context(settings) do
+    context = Context.from_protocol!(settings.protocol)
+
+    if settings.home_dir, do: Context.set_engine_home_dir!(context, settings.home_dir)
+    if settings.pinentry_mode, do: Context.set_pinentry_mode!(context, settings.pinentry_mode)
+    if settings.configure, do: settings.configure.(context)
+
+    context
+  end
+end
@@ -0,0 +1,32 @@
+defmodule ExGpgme.Results.DecryptionResult do
+  @moduledoc """
+  Decryption Result
+  """
+
+  alias ExGpgme.Results.Recipient
+
+  @enforce_keys [
+    :unsupported_algorithm,
+    :is_wrong_key_usage,
+    :is_de_vs,
+    :is_mime,
+    :legacy_cipher_no_mdc,
+    :filename,
+    :symmetric_key_algorithm,
+    :session_key,
+    :recipients
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          unsupported_algorithm: String.t() | nil,
+          is_wrong_key_usage: boolean,
+          is_de_vs: boolean,
+          is_mime: boolean,
+          legacy_cipher_no_mdc: boolean,
+          filename: String.t() | nil,
+          symmetric_key_algorithm: String.t() | nil,
+          session_key: String.t() | nil,
+          recipients: [Recipient.t()]
+        }
+end
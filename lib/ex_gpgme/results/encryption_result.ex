@@ -0,0 +1,16 @@
+defmodule ExGpgme.Results.EncryptionResult do
+  @moduledoc """
+  Encryption Result
+  """
+
+  alias ExGpgme.Results.InvalidKey
+
+  @enforce_keys [
+    :invalid_recipients
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          invalid_recipients: [InvalidKey.t()]
+        }
+end
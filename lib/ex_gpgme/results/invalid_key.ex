@@ -0,0 +1,16 @@
+defmodule ExGpgme.Results.InvalidKey do
+  @moduledoc """
+  Recipient or signer key that could not be used
+  """
+
+  @enforce_keys [
+    :fingerprint,
+    :reason
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          fingerprint: String.t() | nil,
+          reason: ExGpgme.Error.t() | nil
+        }
+end
@@ -0,0 +1,20 @@
+defmodule ExGpgme.Results.KeyGenerationResult do
+  @moduledoc """
+  Result struct for key and subkey creation.
+  """
+
+  @enforce_keys [
+    :fingerprint,
+    :primary_created,
+    :sub_created,
+    :uid_created
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          fingerprint: String.t() | nil,
+          primary_created: boolean,
+          sub_created: boolean,
+          uid_created: boolean
+        }
+end
@@ -0,0 +1,24 @@
+defmodule ExGpgme.Results.NewSignature do
+  @moduledoc """
+  Signature created by a signing operation
+  """
+
+  @enforce_keys [
+    :fingerprint,
+    :mode,
+    :key_algorithm,
+    :hash_algorithm,
+    :creation_time,
+    :signature_class
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          fingerprint: String.t() | nil,
+          mode: ExGpgme.sign_mode(),
+          key_algorithm: ExGpgme.key_algorithm(),
+          hash_algorithm: ExGpgme.hash_algorithm(),
+          creation_time: non_neg_integer | nil,
+          signature_class: non_neg_integer
+        }
+end
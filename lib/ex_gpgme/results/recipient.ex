@@ -0,0 +1,23 @@
+defmodule ExGpgme.Results.Recipient do
+  @moduledoc """
+  Recipient of an encrypted message
+  """
+
+  @enforce_keys [
+    :key_id,
+    :algorithm,
+    :status
+  ]
+  defstruct @enforce_keys
+
+  @typedoc """
+  `:ok` if the message could be decrypted with the key of the recipient.
+  """
+  @type status :: :ok | {:error, ExGpgme.Error.t()}
+
+  @type t :: %__MODULE__{
+          key_id: String.t() | nil,
+          algorithm: ExGpgme.key_algorithm(),
+          status: status
+        }
+end
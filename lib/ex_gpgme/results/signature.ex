@@ -40,11 +40,11 @@ defmodule ExGpgme.Results.Signature do
           pka_trust: Results.pka_trust(),
           pka_address: String.t() | nil,
           validity: ExGpgme.validity(),
-          nonvalidity_reason: String.t() | nil,
+          nonvalidity_reason: ExGpgme.Error.t() | nil,
           key_algorithm: ExGpgme.key_algorithm(),
           hash_algorithm: ExGpgme.hash_algorithm(),
           policy_url: String.t() | nil,
           notations: [SignatureNotation.t()],
-          key: Key.t() | nil
+          key: Key.resource() | nil
         }
 end
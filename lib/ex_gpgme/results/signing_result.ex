@@ -0,0 +1,18 @@
+defmodule ExGpgme.Results.SigningResult do
+  @moduledoc """
+  Signing Result
+  """
+
+  alias ExGpgme.Results.{InvalidKey, NewSignature}
+
+  @enforce_keys [
+    :new_signatures,
+    :invalid_signers
+  ]
+  defstruct @enforce_keys
+
+  @type t :: %__MODULE__{
+          new_signatures: [NewSignature.t()],
+          invalid_signers: [InvalidKey.t()]
+        }
+end
@@ -0,0 +1,96 @@
+use gpgme;
+use gpgme_sys as ffi;
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use context::resource;
+use error::XError;
+
+enum CancelState {
+    Pending,
+    Running(ffi::gpgme_ctx_t),
+    Done
+}
+
+// gpgme forgets a cancel issued before the operation reached the engine, so the request is recorded here as well.
+// The data callbacks of the operation check it and an accepted cancel always ends the operation with CANCELED.
+pub struct CancelHandle {
+    requested: AtomicBool,
+    state: Mutex<CancelState>
+}
+unsafe impl Send for CancelHandle {}
+unsafe impl Sync for CancelHandle {}
+
+impl CancelHandle {
+    pub fn pending() -> CancelHandle {
+        CancelHandle{requested: AtomicBool::new(false), state: Mutex::new(CancelState::Pending)}
+    }
+
+    pub fn running(raw: ffi::gpgme_ctx_t) -> CancelHandle {
+        CancelHandle{requested: AtomicBool::new(false), state: Mutex::new(CancelState::Running(raw))}
+    }
+
+    // Returns false if the operation was canceled before it started.
+    pub fn start(&self, raw: ffi::gpgme_ctx_t) -> bool {
+        let mut state = self.state.lock().unwrap();
+
+        if self.is_requested() {
+            *state = CancelState::Done;
+            return false;
+        }
+
+        *state = CancelState::Running(raw);
+        true
+    }
+
+    // Has to be called before the context of the operation is dropped.
+    pub fn finish<T>(&self, result: gpgme::Result<T>) -> gpgme::Result<T> {
+        *self.state.lock().unwrap() = CancelState::Done;
+
+        if self.is_requested() {
+            Err(gpgme::Error::CANCELED)
+        } else {
+            result
+        }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    pub fn check(&self) -> io::Result<()> {
+        if self.is_requested() {
+            Err(io::Error::new(io::ErrorKind::Other, "operation canceled"))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn cancel(&self) -> Result<(), XError> {
+        let state = self.state.lock().unwrap();
+
+        match *state {
+            CancelState::Pending => {
+                self.requested.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+            CancelState::Running(raw) => {
+                self.requested.store(true, Ordering::SeqCst);
+                resource::cancel(raw).map_err(XError::new)
+            },
+            CancelState::Done => Err(XError::with_message(gpgme::Error::INV_STATE, "Operation is finished"))
+        }
+    }
+}
+
+pub struct CancelableReader<R> {
+    pub reader: R,
+    pub handle: Arc<CancelHandle>
+}
+
+impl<R: Read> Read for CancelableReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.handle.check()?;
+        self.reader.read(buf)
+    }
+}
@@ -0,0 +1,220 @@
+use rustler::{Encoder, Env, Term};
+use rustler::env::OwnedEnv;
+use rustler::resource::ResourceArc;
+use rustler::types::LocalPid;
+use gpgme;
+use gpgme::{Context, PassphraseRequest, ProgressInfo};
+use std::ffi::CStr;
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+use context::resource::ContextNifResource;
+use operation::OperationResource;
+
+mod atoms {
+    atoms! {
+        gpgme_passphrase,
+        gpgme_progress,
+        gpgme_status
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Callbacks {
+    pub passphrase: Option<PassphraseCallback>,
+    pub progress: Option<LocalPid>,
+    pub status: Option<LocalPid>
+}
+
+#[derive(Clone)]
+pub struct PassphraseCallback {
+    pub pid: LocalPid,
+    pub timeout: Duration
+}
+
+pub struct PassphraseRequestResource {
+    pub sender: Mutex<Option<Sender<Option<String>>>>
+}
+
+// Progress and status messages name the context of a synchronous operation or session, or the background operation.
+#[derive(Clone)]
+pub enum Origin {
+    Context(ResourceArc<ContextNifResource>),
+    Operation(ResourceArc<OperationResource>)
+}
+
+impl Encoder for Origin {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match *self {
+            Origin::Context(ref context_arc) => context_arc.encode(env),
+            Origin::Operation(ref operation_arc) => operation_arc.encode(env)
+        }
+    }
+}
+
+type Message = Box<dyn for<'b> FnOnce(Env<'b>) -> Term<'b> + Send>;
+
+// Scheduler threads can only send with the env of the running NIF, which must not leave the NIF call. Messages of
+// NIF calls are built and sent by a relay thread instead, which keeps them in order.
+lazy_static! {
+    static ref RELAY: Mutex<Sender<(LocalPid, Message)>> = {
+        let (sender, receiver) = channel::<(LocalPid, Message)>();
+
+        thread::spawn(move || {
+            let mut env = OwnedEnv::new();
+
+            for (pid, message) in receiver {
+                env.send_and_clear(&pid, message);
+            }
+        });
+
+        Mutex::new(sender)
+    };
+}
+
+#[derive(Clone, Copy)]
+pub enum Messenger {
+    Nif,
+    Thread
+}
+
+impl Messenger {
+    pub fn send<F>(&self, pid: &LocalPid, message: F)
+        where F: for<'b> FnOnce(Env<'b>) -> Term<'b> + Send + 'static
+    {
+        match *self {
+            Messenger::Nif => {
+                let _ = RELAY.lock().unwrap().send((pid.clone(), Box::new(message)));
+            },
+            Messenger::Thread => OwnedEnv::new().send_and_clear(pid, message)
+        }
+    }
+}
+
+struct PassphraseHandler {
+    messenger: Messenger,
+    callback: PassphraseCallback
+}
+
+impl gpgme::PassphraseProvider for PassphraseHandler {
+    fn get_passphrase(&mut self, request: PassphraseRequest, out: &mut dyn Write) -> gpgme::Result<()> {
+        let (sender, receiver) = channel();
+        let request_arc = ResourceArc::new(PassphraseRequestResource{
+            sender: Mutex::new(Some(sender))
+        });
+
+        let uid_hint = request.user_id_hint().ok().map(String::from);
+        let info = request.description().ok().map(String::from);
+        let prev_was_bad = request.prev_attempt_failed;
+        let message_arc = request_arc.clone();
+
+        self.messenger.send(&self.callback.pid, move | env | {
+            (atoms::gpgme_passphrase(), message_arc, uid_hint, info, prev_was_bad).encode(env)
+        });
+
+        let reply = receiver.recv_timeout(self.callback.timeout);
+        request_arc.sender.lock().unwrap().take();
+
+        match reply {
+            Ok(Some(passphrase)) => {
+                out.write_all(passphrase.as_bytes())?;
+                out.write_all(b"\n")?;
+                Ok(())
+            },
+            Ok(None) | Err(RecvTimeoutError::Disconnected) => Err(gpgme::Error::CANCELED),
+            Err(RecvTimeoutError::Timeout) => Err(gpgme::Error::TIMEOUT)
+        }
+    }
+}
+
+struct ProgressHandler {
+    messenger: Messenger,
+    origin: Origin,
+    pid: LocalPid
+}
+
+impl gpgme::ProgressReporter for ProgressHandler {
+    fn report(&mut self, info: ProgressInfo) {
+        let what = info.what().ok().map(String::from);
+        let (typ, current, total) = (info.typ, info.current, info.total);
+        let origin = self.origin.clone();
+
+        self.messenger.send(&self.pid, move | env | {
+            (atoms::gpgme_progress(), origin, what, typ, current, total).encode(env)
+        });
+    }
+}
+
+struct StatusHandler {
+    messenger: Messenger,
+    origin: Origin,
+    pid: LocalPid
+}
+
+impl gpgme::StatusHandler for StatusHandler {
+    fn handle(&mut self, keyword: Option<&CStr>, args: Option<&CStr>) -> gpgme::Result<()> {
+        let keyword = keyword.map(| keyword | keyword.to_string_lossy().into_owned());
+        let args = args.map(| args | args.to_string_lossy().into_owned());
+        let origin = self.origin.clone();
+
+        self.messenger.send(&self.pid, move | env | {
+            (atoms::gpgme_status(), origin, keyword, args).encode(env)
+        });
+
+        Ok(())
+    }
+}
+
+pub fn with_callbacks<R, F>(messenger: Messenger, origin: &Origin, callbacks: &Callbacks, context: &mut Context, f: F) -> R
+    where F: FnOnce(&mut Context) -> R
+{
+    match callbacks.passphrase {
+        Some(ref callback) => {
+            let handler = PassphraseHandler{messenger: messenger, callback: callback.clone()};
+            context.with_passphrase_provider(handler, | context | with_progress(messenger, origin, callbacks, context, f))
+        },
+        None => with_progress(messenger, origin, callbacks, context, f)
+    }
+}
+
+fn with_progress<R, F>(messenger: Messenger, origin: &Origin, callbacks: &Callbacks, context: &mut Context, f: F) -> R
+    where F: FnOnce(&mut Context) -> R
+{
+    match callbacks.progress {
+        Some(ref pid) => {
+            let handler = ProgressHandler{messenger: messenger, origin: origin.clone(), pid: pid.clone()};
+            context.with_progress_reporter(handler, | context | with_status(messenger, origin, callbacks, context, f))
+        },
+        None => with_status(messenger, origin, callbacks, context, f)
+    }
+}
+
+// gpgme only forwards a few status lines unless full-status is set.
+fn with_status<R, F>(messenger: Messenger, origin: &Origin, callbacks: &Callbacks, context: &mut Context, f: F) -> R
+    where F: FnOnce(&mut Context) -> R
+{
+    match callbacks.status {
+        Some(ref pid) => {
+            let full_status = context.get_flag("full-status").ok().map(String::from).unwrap_or_default();
+            let _ = context.set_flag("full-status", "1");
+
+            let handler = StatusHandler{messenger: messenger, origin: origin.clone(), pid: pid.clone()};
+            let result = context.with_status_handler(handler, f);
+
+            let _ = context.set_flag("full-status", full_status);
+            result
+        },
+        None => f(context)
+    }
+}
+
+macro_rules! with_callbacks {
+    ($context_arc:expr, $context:ident, $body:expr) => ({
+        let callbacks = $context_arc.callbacks.read().unwrap().clone();
+        let origin = ::context::callbacks::Origin::Context($context_arc.clone());
+
+        ::context::callbacks::with_callbacks(::context::callbacks::Messenger::Nif, &origin, &callbacks, &mut $context, | $context | $body)
+    })
+}
@@ -1,3 +1,14 @@
+use rustler::{Encoder, Env, Error, NifResult, Term};
+use rustler::types::binary::{Binary, OwnedBinary};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static OUTPUT_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
 pub mod atoms {
     atoms! {
         ok,
@@ -9,16 +20,72 @@ macro_rules! try_gpgme {
     ($expr:expr) => (match $expr {
         Ok(val) => val,
         Err(err) => {
-            return Err(rustler::Error::Term(Box::new(err.description().into_owned())))
+            return Err(rustler::Error::Term(Box::new(::error::XError::new(err))))
         }
     })
 }
 
-macro_rules! decode_context_result {
-    ($name:ident, $env:ident) => (
-        match String::from_utf8($name) {
-            Ok(string) => Ok((::context::helpers::atoms::ok(), string).encode($env)),
-            Err(_) => Ok((::context::helpers::atoms::error(), String::from("Could not decode cyphertext to utf8")).encode($env))
+macro_rules! try_io {
+    ($expr:expr) => (match $expr {
+        Ok(val) => val,
+        Err(err) => {
+            return Err(rustler::Error::Term(Box::new(::error::XError::new(::gpgme::Error::from(err)))))
         }
-    )
+    })
+}
+
+pub fn to_binary<'a>(env: Env<'a>, data: &[u8]) -> NifResult<Binary<'a>> {
+    let mut binary = match OwnedBinary::new(data.len()) {
+        Some(binary) => binary,
+        None => return Err(Error::RaiseAtom("enomem"))
+    };
+    binary.as_mut_slice().copy_from_slice(data);
+
+    Ok(Binary::from_owned(binary, env))
+}
+
+pub fn encode_binary_result<'a>(env: Env<'a>, data: &[u8]) -> NifResult<Term<'a>> {
+    Ok((atoms::ok(), to_binary(env, data)?).encode(env))
+}
+
+// Output is written next to the target and renamed over it on success, so a failed operation leaves the target
+// untouched. The temporary file is removed when the value is dropped without being persisted.
+pub struct OutputFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    pub file: File,
+    persisted: bool
+}
+
+impl OutputFile {
+    pub fn create(path: &str) -> io::Result<OutputFile> {
+        let path = PathBuf::from(path);
+        let file_name = match path.file_name() {
+            Some(file_name) => file_name.to_string_lossy().into_owned(),
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "output path has no file name"))
+        };
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        let counter = OUTPUT_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temp_path = directory.join(format!(".{}.{}.{}.tmp", file_name, process::id(), counter));
+
+        let file = OpenOptions::new().write(true).create_new(true).open(&temp_path)?;
+
+        Ok(OutputFile{path: path, temp_path: temp_path, file: file, persisted: false})
+    }
+
+    pub fn persist(mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        fs::rename(&self.temp_path, &self.path)?;
+        self.persisted = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
 }
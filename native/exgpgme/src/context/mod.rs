@@ -1,28 +1,51 @@
-use rustler::{Atom, Encoder, Env, Error, NifResult, Term};
+use rustler::{Atom, Binary, Encoder, Env, Error, NifResult, Term};
 use rustler::resource::ResourceArc;
 use rustler::types::list::ListIterator;
-use gpgme::{Context, EncryptFlags};
+use rustler::types::LocalPid;
+use gpgme;
+use gpgme::{Context, Data, EncryptFlags};
 use gpgme::keys::Key;
 use std::ops::Deref;
 use results::verification_result::transform_verification_result;
+use results::decryption_result::transform_decryption_result;
+use results::encryption_result::{last_encryption_result, transform_encryption_result};
+use results::signing_result::{last_signing_result, transform_signing_result};
 use keys;
+use keys::cursor;
 use protocol;
 use protocol::XProtocol;
 use encrypt_flags;
+use decrypt_flags;
 use engine;
 use pinentry_mode;
 use pinentry_mode::XPinentryMode;
 use sign_mode;
+use keylist_mode;
+use keylist_mode::XKeyListMode;
 use results::import_result::transform_import_result;
+use results::key_generation_result::transform_key_generation_result;
+use create_key_flags;
+use expiration;
+use expiration::ExpirationTarget;
+use key_signing_flags;
+use export_mode;
+use session;
+use operation;
+use error::XError;
+use std::time::Duration;
+use std::fs::File;
+use std::io;
+use self::helpers::OutputFile;
 
 #[macro_use] pub mod helpers;
 #[macro_use] pub mod resource;
+#[macro_use] pub mod callbacks;
 
 mod atoms {
     atoms! {
         ok,
         error,
-        not_set
+        done
     }
 }
 
@@ -44,19 +67,20 @@ pub fn from_protocol(protocol_arg: Term) -> NifResult<FromProtocolResponse> {
     })
 }
 
-#[rustler::nif]
+// Everything that takes the context lock runs on dirty schedulers, a running operation may hold the lock.
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn get_protocol(context_arc: ResourceArc<resource::ContextNifResource>) -> NifResult<XProtocol> {
     unpack_immutable_context!(context, context_arc);
     Ok(XProtocol(context.protocol()))
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn offline(context_arc: ResourceArc<resource::ContextNifResource>) -> bool {
     unpack_immutable_context!(context, context_arc);
     context.offline()
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_offline(context_arc: ResourceArc<resource::ContextNifResource>, yes: bool) -> Atom {
     unpack_mutable_context!(context, context_arc);
     context.set_offline(yes);
@@ -64,13 +88,13 @@ pub fn set_offline(context_arc: ResourceArc<resource::ContextNifResource>, yes:
     atoms::ok()
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn text_mode(context_arc: ResourceArc<resource::ContextNifResource>) -> bool {
     unpack_immutable_context!(context, context_arc);
     context.text_mode()
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_text_mode(context_arc: ResourceArc<resource::ContextNifResource>, yes: bool) -> Atom {
     unpack_mutable_context!(context, context_arc);
     context.set_text_mode(yes);
@@ -78,13 +102,13 @@ pub fn set_text_mode(context_arc: ResourceArc<resource::ContextNifResource>, yes
     atoms::ok()
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn armor(context_arc: ResourceArc<resource::ContextNifResource>) -> bool {
     unpack_immutable_context!(context, context_arc);
     context.armor()
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_armor(context_arc: ResourceArc<resource::ContextNifResource>, yes: bool) -> Atom {
     unpack_mutable_context!(context, context_arc);
     context.set_armor(yes);
@@ -98,7 +122,7 @@ pub struct GetFlagResponse {
     flag: String,
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn get_flag(context_arc: ResourceArc<resource::ContextNifResource>, name: String) -> NifResult<GetFlagResponse> {
     unpack_immutable_context!(context, context_arc);
 
@@ -107,11 +131,11 @@ pub fn get_flag(context_arc: ResourceArc<resource::ContextNifResource>, name: St
             ok: atoms::ok(),
             flag: String::from(result)
         }),
-        Err(_) => Err(Error::Term(Box::new(atoms::not_set())))
+        Err(_) => Err(Error::Term(Box::new(XError::with_message(gpgme::Error::NOT_FOUND, "Flag is not set"))))
     }
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_flag(context_arc: ResourceArc<resource::ContextNifResource>, name: String, value: String) -> NifResult<Atom> {
     unpack_mutable_context!(context, context_arc);
 
@@ -120,18 +144,18 @@ pub fn set_flag(context_arc: ResourceArc<resource::ContextNifResource>, name: St
     Ok(atoms::ok())
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn engine_info(env: Env, context_arc: ResourceArc<resource::ContextNifResource>) -> NifResult<Term> {
     unpack_immutable_context!(context, context_arc);
     Ok(
         match engine::engine_info_to_term(context.engine_info(), env) {
             Ok(result) => (atoms::ok(), result).encode(env),
-            Err(_) => (atoms::error(), String::from("Could not decode cyphertext to utf8")).encode(env)
+            Err(_) => (atoms::error(), XError::decode("engine info")).encode(env)
         }
     )
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_engine_path(context_arc: ResourceArc<resource::ContextNifResource>, path: String) -> NifResult<Atom> {
     unpack_mutable_context!(context, context_arc);
     try_gpgme!(context.set_engine_path(path));
@@ -140,7 +164,7 @@ pub fn set_engine_path(context_arc: ResourceArc<resource::ContextNifResource>, p
 }
 
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_engine_home_dir(context_arc: ResourceArc<resource::ContextNifResource>, home_dir: String) -> NifResult<Atom> {
     unpack_mutable_context!(context, context_arc);
     try_gpgme!(context.set_engine_home_dir(home_dir));
@@ -149,14 +173,14 @@ pub fn set_engine_home_dir(context_arc: ResourceArc<resource::ContextNifResource
 }
 
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn get_pinentry_mode(context_arc: ResourceArc<resource::ContextNifResource>) -> NifResult<XPinentryMode> {
     unpack_immutable_context!(context, context_arc);
     Ok(XPinentryMode(context.pinentry_mode()))
 }
 
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyIo")]
 pub fn set_pinentry_mode(context_arc: ResourceArc<resource::ContextNifResource>, mode_arg: Term) -> NifResult<Atom> {
     unpack_mutable_context!(context, context_arc);
 
@@ -168,14 +192,148 @@ pub fn set_pinentry_mode(context_arc: ResourceArc<resource::ContextNifResource>,
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
-pub fn import(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, data: String) -> NifResult<Term> {
+pub fn get_keylist_mode(context_arc: ResourceArc<resource::ContextNifResource>) -> NifResult<XKeyListMode> {
+    unpack_immutable_context!(context, context_arc);
+    Ok(XKeyListMode(context.key_list_mode()))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn set_keylist_mode(context_arc: ResourceArc<resource::ContextNifResource>, mode_arg: Term) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    let mode = keylist_mode::arg_to_keylist_mode(mode_arg.decode::<ListIterator>()?)?;
+
+    try_gpgme!(context.set_key_list_mode(mode));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn add_signer(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    try_gpgme!(context.add_signer(&key_arc.key));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn clear_signers(context_arc: ResourceArc<resource::ContextNifResource>) -> Atom {
+    unpack_mutable_context!(context, context_arc);
+    context.clear_signers();
+
+    atoms::ok()
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn signers(context_arc: ResourceArc<resource::ContextNifResource>) -> Vec<ResourceArc<keys::KeyResource>> {
+    unpack_immutable_context!(context, context_arc);
+
+    context.signers().map(keys::wrap_key).collect()
+}
+
+#[rustler::nif]
+pub fn set_passphrase_provider(context_arc: ResourceArc<resource::ContextNifResource>, pid: LocalPid, timeout: u64) -> Atom {
+    let mut callbacks = context_arc.callbacks.write().unwrap();
+
+    callbacks.passphrase = Some(callbacks::PassphraseCallback{
+        pid: pid,
+        timeout: Duration::from_millis(timeout)
+    });
+
+    atoms::ok()
+}
+
+#[rustler::nif]
+pub fn clear_passphrase_provider(context_arc: ResourceArc<resource::ContextNifResource>) -> Atom {
+    context_arc.callbacks.write().unwrap().passphrase = None;
+
+    atoms::ok()
+}
+
+#[rustler::nif]
+pub fn reply_passphrase(env: Env, request_arc: ResourceArc<callbacks::PassphraseRequestResource>, passphrase: Option<String>) -> Term {
+    match request_arc.sender.lock().unwrap().take() {
+        Some(sender) => {
+            let _ = sender.send(passphrase);
+            atoms::ok().encode(env)
+        },
+        None => (atoms::error(), XError::with_message(gpgme::Error::INV_STATE, "Request is closed")).encode(env)
+    }
+}
+
+#[rustler::nif]
+pub fn set_progress_handler(context_arc: ResourceArc<resource::ContextNifResource>, pid: LocalPid) -> Atom {
+    context_arc.callbacks.write().unwrap().progress = Some(pid);
+
+    atoms::ok()
+}
+
+#[rustler::nif]
+pub fn clear_progress_handler(context_arc: ResourceArc<resource::ContextNifResource>) -> Atom {
+    context_arc.callbacks.write().unwrap().progress = None;
+
+    atoms::ok()
+}
+
+#[rustler::nif]
+pub fn set_status_handler(context_arc: ResourceArc<resource::ContextNifResource>, pid: LocalPid) -> Atom {
+    context_arc.callbacks.write().unwrap().status = Some(pid);
+
+    atoms::ok()
+}
+
+#[rustler::nif]
+pub fn clear_status_handler(context_arc: ResourceArc<resource::ContextNifResource>) -> Atom {
+    context_arc.callbacks.write().unwrap().status = None;
+
+    atoms::ok()
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn import<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, data: Binary<'a>) -> NifResult<Term<'a>> {
     unpack_mutable_context!(context, context_arc);
 
-    let result = try_gpgme!(context.import(data));
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.import(data.as_slice())));
 
     Ok((atoms::ok(), transform_import_result(env, result)).encode(env))
 }
 
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn export_keys<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, keys_arg: Term<'a>, mode_arg: Term<'a>) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+
+    let mode = export_mode::arg_to_export_mode(mode_arg.decode::<ListIterator>()?)?;
+    let is_extern = mode.contains(gpgme::ExportMode::EXTERN);
+
+    let mut data: Vec<u8> = Vec::new();
+
+    let result = match keys_arg.decode::<Vec<ResourceArc<keys::KeyResource>>>() {
+        Ok(ref key_arcs) if !key_arcs.is_empty() => {
+            let export_keys: Vec<&Key> = key_arcs.iter().map(| key_arc | &key_arc.key).collect();
+
+            with_callbacks!(context_arc, context, if is_extern {
+                context.export_extern(export_keys, mode)
+            } else {
+                context.export(export_keys, mode, &mut data)
+            })
+        },
+        _ => {
+            let patterns: Vec<String> = keys_arg.decode()?;
+
+            with_callbacks!(context_arc, context, if is_extern {
+                context.export_all_extern(patterns, mode)
+            } else {
+                context.export_all(patterns, mode, &mut data)
+            })
+        }
+    };
+
+    try_gpgme!(result);
+
+    helpers::encode_binary_result(env, &data)
+}
+
 #[rustler::nif(schedule = "DirtyIo")]
 pub fn find_key(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, fingerprint: String) -> NifResult<Term> {
     unpack_mutable_context!(context, context_arc);
@@ -186,7 +344,79 @@ pub fn find_key(env: Env, context_arc: ResourceArc<resource::ContextNifResource>
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
-pub fn encrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, key_list_arg: Term, data: String, flags_arg: Term) -> NifResult<Term<'a>> {
+pub fn list_keys(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, patterns: Vec<String>, secret_only: bool) -> NifResult<Term> {
+    unpack_mutable_context!(context, context_arc);
+
+    let key_list = if secret_only {
+        try_gpgme!(context.find_secret_keys(patterns))
+    } else {
+        try_gpgme!(context.find_keys(patterns))
+    };
+
+    let mut result: Vec<ResourceArc<keys::KeyResource>> = Vec::new();
+    for key in key_list {
+        result.push(keys::wrap_key(try_gpgme!(key)));
+    }
+
+    Ok((atoms::ok(), result).encode(env))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn open_key_cursor(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, patterns: Vec<String>, secret_only: bool) -> NifResult<Term> {
+    unpack_immutable_context!(context, context_arc);
+
+    let cursor_context = try_gpgme!(resource::duplicate_context(&context));
+
+    Ok((atoms::ok(), cursor::spawn_key_cursor(cursor_context, patterns, secret_only)).encode(env))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn next_keys(env: Env, cursor_arc: ResourceArc<cursor::KeyCursorResource>, count: usize) -> NifResult<Term> {
+    let receiver_guard = cursor_arc.receiver.lock().unwrap();
+
+    let receiver = match *receiver_guard {
+        Some(ref receiver) => receiver,
+        None => return Ok(atoms::done().encode(env))
+    };
+
+    if let Some(err) = cursor_arc.error.lock().unwrap().take() {
+        return Err(Error::Term(Box::new(XError::new(err))));
+    }
+
+    let mut result: Vec<ResourceArc<keys::KeyResource>> = Vec::new();
+    while result.len() < count {
+        match receiver.recv() {
+            Ok(Ok(key)) => result.push(keys::wrap_key(key)),
+            // Keys listed before the error are returned first.
+            Ok(Err(err)) => {
+                if result.is_empty() {
+                    return Err(Error::Term(Box::new(XError::new(err))));
+                }
+
+                *cursor_arc.error.lock().unwrap() = Some(err);
+                break;
+            },
+            Err(_) => break
+        }
+    }
+
+    if result.is_empty() {
+        Ok(atoms::done().encode(env))
+    } else {
+        Ok((atoms::ok(), result).encode(env))
+    }
+}
+
+// Waits for a running next_keys call, which may block on the listing.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn close_key_cursor(cursor_arc: ResourceArc<cursor::KeyCursorResource>) -> Atom {
+    cursor_arc.receiver.lock().unwrap().take();
+
+    atoms::ok()
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn encrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, key_list_arg: Term, data: Binary<'a>, flags_arg: Term) -> NifResult<Term<'a>> {
     unpack_mutable_context!(context, context_arc);
     unpack_key_list!(recipients, key_list_arg);
 
@@ -195,13 +425,19 @@ pub fn encrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<resource::C
     let flags: EncryptFlags = encrypt_flags::arg_to_protocol(flags_arg.decode::<ListIterator>()?)?;
 
     let mut cyphertext: Vec<u8> = Vec::new();
-    try_gpgme!(context.encrypt_with_flags(recipients, data, &mut cyphertext, flags));
-
-    decode_context_result!(cyphertext, env)
+    let result = match with_callbacks!(context_arc, context, context.encrypt_with_flags(recipients, data.as_slice(), &mut cyphertext, flags)) {
+        Ok(result) => result,
+        Err(err) => return encode_encryption_error(env, &context, err)
+    };
+
+    match transform_encryption_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), helpers::to_binary(env, &cyphertext)?, nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
-pub fn sign_and_encrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, key_list_arg: Term, data: String, flags_arg: Term) -> NifResult<Term<'a>> {
+pub fn sign_and_encrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, key_list_arg: Term, data: Binary<'a>, flags_arg: Term) -> NifResult<Term<'a>> {
     unpack_mutable_context!(context, context_arc);
     unpack_key_list!(recipients, key_list_arg);
 
@@ -210,9 +446,313 @@ pub fn sign_and_encrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<re
     let flags: EncryptFlags = encrypt_flags::arg_to_protocol(flags_arg.decode::<ListIterator>()?)?;
 
     let mut cyphertext: Vec<u8> = Vec::new();
-    try_gpgme!(context.sign_and_encrypt_with_flags(recipients, data, &mut cyphertext, flags));
+    let (encryption_result, signing_result) = match with_callbacks!(context_arc, context, context.sign_and_encrypt_with_flags(recipients, data.as_slice(), &mut cyphertext, flags)) {
+        Ok(results) => results,
+        Err(err) => return encode_encryption_error(env, &context, err)
+    };
+
+    let encryption = transform_encryption_result(env, encryption_result);
+    let signing = transform_signing_result(env, signing_result);
+
+    match (encryption, signing) {
+        (Ok(encryption), Ok(signing)) => Ok((atoms::ok(), helpers::to_binary(env, &cyphertext)?, encryption, signing).encode(env)),
+        _ => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+// Failed encryptions report the invalid recipients along with the error.
+fn encode_encryption_error<'a>(env: Env<'a>, context: &Context, err: gpgme::Error) -> NifResult<Term<'a>> {
+    let reason = XError::new(err);
+
+    match last_encryption_result(context) {
+        Some(result) => {
+            if result.invalid_recipients().count() == 0 {
+                return Err(Error::Term(Box::new(reason)));
+            }
+
+            match transform_encryption_result(env, result) {
+                Ok(nif_result) => Ok((atoms::error(), reason, nif_result).encode(env)),
+                Err(_) => Err(Error::Term(Box::new(reason)))
+            }
+        },
+        None => Err(Error::Term(Box::new(reason)))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn encrypt_file<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, key_list_arg: Term, input_path: String, output_path: String, flags_arg: Term) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+    unpack_key_list!(recipients, key_list_arg);
+
+    keys::keys_not_empty(recipients.len())?;
+
+    let flags: EncryptFlags = encrypt_flags::arg_to_protocol(flags_arg.decode::<ListIterator>()?)?;
+
+    let input_file = try_io!(File::open(input_path));
+    let output_file = try_io!(OutputFile::create(&output_path));
+
+    let result = {
+        let mut input = try_gpgme!(Data::from_fd(&input_file));
+        let mut output = try_gpgme!(Data::from_fd(&output_file.file));
+
+        match with_callbacks!(context_arc, context, context.encrypt_with_flags(recipients, &mut input, &mut output, flags)) {
+            Ok(result) => result,
+            Err(err) => return encode_encryption_error(env, &context, err)
+        }
+    };
+
+    try_io!(output_file.persist());
+
+    match transform_encryption_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn decrypt_file(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, input_path: String, output_path: String) -> NifResult<Term> {
+    unpack_mutable_context!(context, context_arc);
+
+    let input_file = try_io!(File::open(input_path));
+    let output_file = try_io!(OutputFile::create(&output_path));
+
+    let result = {
+        let mut input = try_gpgme!(Data::from_fd(&input_file));
+        let mut output = try_gpgme!(Data::from_fd(&output_file.file));
+
+        try_gpgme!(with_callbacks!(context_arc, context, context.decrypt(&mut input, &mut output)))
+    };
+
+    try_io!(output_file.persist());
 
-    decode_context_result!(cyphertext, env)
+    match transform_decryption_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn sign_file<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, mode_arg: Term, input_path: String, output_path: String) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+
+    let mode = sign_mode::arg_to_sign_mode(mode_arg)?;
+
+    let input_file = try_io!(File::open(input_path));
+    let output_file = try_io!(OutputFile::create(&output_path));
+
+    let result = {
+        let mut input = try_gpgme!(Data::from_fd(&input_file));
+        let mut output = try_gpgme!(Data::from_fd(&output_file.file));
+
+        match with_callbacks!(context_arc, context, context.sign(mode, &mut input, &mut output)) {
+            Ok(result) => result,
+            Err(err) => return encode_signing_error(env, &context, err)
+        }
+    };
+
+    try_io!(output_file.persist());
+
+    match transform_signing_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn verify_file(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, input_path: String, output_path: Option<String>) -> NifResult<Term> {
+    unpack_mutable_context!(context, context_arc);
+
+    let input_file = try_io!(File::open(input_path));
+    let mut input = try_gpgme!(Data::from_fd(&input_file));
+
+    let result = match output_path {
+        Some(output_path) => {
+            let output_file = try_io!(OutputFile::create(&output_path));
+
+            let result = {
+                let mut output = try_gpgme!(Data::from_fd(&output_file.file));
+
+                try_gpgme!(with_callbacks!(context_arc, context, context.verify_opaque(&mut input, &mut output)))
+            };
+
+            try_io!(output_file.persist());
+            result
+        },
+        None => {
+            let mut output = try_gpgme!(Data::from_writer(io::sink()).map_err(| err | err.error()));
+
+            try_gpgme!(with_callbacks!(context_arc, context, context.verify_opaque(&mut input, &mut output)))
+        }
+    };
+
+    match transform_verification_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+// Copying the context waits for operations holding its lock.
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn start_operation<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, operation_arg: Term<'a>, data: Binary<'a>) -> NifResult<Term<'a>> {
+    unpack_immutable_context!(context, context_arc);
+
+    let operation = session::arg_to_operation(operation_arg)?;
+    let operation_context = try_gpgme!(resource::duplicate_context(&context));
+    let callbacks = context_arc.callbacks.read().unwrap().clone();
+
+    Ok(operation::spawn_operation(env.pid(), operation_context, callbacks, operation, data.as_slice().to_vec()).encode(env))
+}
+
+#[rustler::nif]
+pub fn cancel(env: Env, arg: Term) -> NifResult<Term> {
+    let result = if let Ok(context_arc) = arg.decode::<ResourceArc<resource::ContextNifResource>>() {
+        resource::cancel(context_arc.raw).map_err(XError::new)
+    } else if let Ok(operation_arc) = arg.decode::<ResourceArc<operation::OperationResource>>() {
+        operation_arc.cancel.cancel()
+    } else if let Ok(session_arc) = arg.decode::<ResourceArc<session::SessionResource>>() {
+        session_arc.cancel.cancel()
+    } else {
+        arg.decode::<ResourceArc<cursor::KeyCursorResource>>()?.cancel.cancel()
+    };
+
+    match result {
+        Ok(_) => Ok(atoms::ok().encode(env)),
+        Err(err) => Ok((atoms::error(), err).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn open_session(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, operation_arg: Term) -> NifResult<Term> {
+    unpack_immutable_context!(context, context_arc);
+
+    let operation = session::arg_to_operation(operation_arg)?;
+    let session_context = try_gpgme!(resource::duplicate_context(&context));
+    let callbacks = context_arc.callbacks.read().unwrap().clone();
+    let origin = callbacks::Origin::Context(context_arc.clone());
+
+    Ok((atoms::ok(), session::spawn_session(session_context, origin, callbacks, operation)).encode(env))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn write_session<'a>(env: Env<'a>, session_arc: ResourceArc<session::SessionResource>, chunk: Binary<'a>) -> NifResult<Term<'a>> {
+    let state_guard = session_arc.state.lock().unwrap();
+
+    match *state_guard {
+        Some(ref state) => helpers::encode_binary_result(env, &session::write(state, chunk.as_slice())),
+        None => Ok((atoms::error(), XError::with_message(gpgme::Error::INV_STATE, "Session is finished")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn finish_session(env: Env, session_arc: ResourceArc<session::SessionResource>) -> NifResult<Term> {
+    let state = match session_arc.state.lock().unwrap().take() {
+        Some(state) => state,
+        None => return Ok((atoms::error(), XError::with_message(gpgme::Error::INV_STATE, "Session is finished")).encode(env))
+    };
+
+    let (output, result) = try_gpgme!(session::finish(state));
+    let output_binary = helpers::to_binary(env, &output)?;
+
+    match session::transform_operation_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), output_binary, nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn create_key(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, userid: String, algorithm: String, expires: u64, flags_arg: Term) -> NifResult<Term> {
+    unpack_mutable_context!(context, context_arc);
+
+    let flags = create_key_flags::arg_to_create_key_flags(flags_arg.decode::<ListIterator>()?)?;
+
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.create_key_with_flags(userid, algorithm, Duration::from_secs(expires), flags)));
+
+    match transform_key_generation_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("fingerprint")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn create_subkey(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, algorithm: String, expires: u64, flags_arg: Term) -> NifResult<Term> {
+    unpack_mutable_context!(context, context_arc);
+
+    let flags = create_key_flags::arg_to_create_key_flags(flags_arg.decode::<ListIterator>()?)?;
+
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.create_subkey_with_flags(&key_arc.key, algorithm, Duration::from_secs(expires), flags)));
+
+    match transform_key_generation_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("fingerprint")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn add_uid(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, userid: String) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    try_gpgme!(with_callbacks!(context_arc, context, context.add_uid(&key_arc.key, userid)));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn revoke_uid(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, userid: String) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    try_gpgme!(with_callbacks!(context_arc, context, context.revoke_uid(&key_arc.key, userid)));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn set_uid_flag(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, userid: String, name: String, value: Option<String>) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    try_gpgme!(with_callbacks!(context_arc, context, context.set_uid_flag(&key_arc.key, userid, name, value)));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn set_key_expire(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, expires_arg: Term, subkeys_arg: Term) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    let expires = expiration::arg_to_expiration(expires_arg)?;
+
+    let target = expiration::arg_to_expiration_target(subkeys_arg)?;
+
+    if let ExpirationTarget::Subkeys(ref fingerprints) = target {
+        keys::keys_not_empty(fingerprints.len())?;
+    }
+
+    try_gpgme!(with_callbacks!(context_arc, context, match target {
+        ExpirationTarget::Primary => context.set_expire(&key_arc.key, expires, Vec::<String>::new()),
+        ExpirationTarget::All => context.set_expire_all(&key_arc.key, expires),
+        ExpirationTarget::Subkeys(fingerprints) => context.set_expire(&key_arc.key, expires, fingerprints)
+    }));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn sign_key(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, userids: Vec<String>, expires: u64, flags_arg: Term) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    let flags = key_signing_flags::arg_to_key_signing_flags(flags_arg.decode::<ListIterator>()?)?;
+
+    try_gpgme!(with_callbacks!(context_arc, context, context.sign_key_with_flags(&key_arc.key, userids, Duration::from_secs(expires), flags)));
+
+    Ok(atoms::ok())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn revoke_signature(context_arc: ResourceArc<resource::ContextNifResource>, key_arc: ResourceArc<keys::KeyResource>, signing_key_arc: ResourceArc<keys::KeyResource>, userids: Vec<String>) -> NifResult<Atom> {
+    unpack_mutable_context!(context, context_arc);
+
+    try_gpgme!(with_callbacks!(context_arc, context, context.revoke_signature(&key_arc.key, &signing_key_arc.key, userids)));
+
+    Ok(atoms::ok())
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
@@ -223,7 +763,7 @@ pub fn delete_key(context_arc: ResourceArc<resource::ContextNifResource>, key_ar
     let key_ref = key_arc.deref();
     let key: &Key = &key_ref.key;
 
-    try_gpgme!(context.delete_key(key));
+    try_gpgme!(with_callbacks!(context_arc, context, context.delete_key(key)));
 
     Ok(atoms::ok())
 }
@@ -236,43 +776,130 @@ pub fn delete_secret_key(context_arc: ResourceArc<resource::ContextNifResource>,
     let key_ref = key_arc.deref();
     let key: &Key = &key_ref.key;
 
-    try_gpgme!(context.delete_secret_key(key));
+    try_gpgme!(with_callbacks!(context_arc, context, context.delete_secret_key(key)));
 
     Ok(atoms::ok())
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
-pub fn decrypt(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, cyphertext: String) -> NifResult<Term> {
+pub fn decrypt<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, cyphertext: Binary<'a>) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+
+    let mut cleartext: Vec<u8> = Vec::new();
+
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.decrypt(cyphertext.as_slice(), &mut cleartext)));
+
+    match transform_decryption_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), helpers::to_binary(env, &cleartext)?, nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn decrypt_with_flags<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, cyphertext: Binary<'a>, flags_arg: Term<'a>) -> NifResult<Term<'a>> {
     unpack_mutable_context!(context, context_arc);
 
+    let flags = decrypt_flags::arg_to_decrypt_flags(flags_arg.decode::<ListIterator>()?)?;
+
     let mut cleartext: Vec<u8> = Vec::new();
 
-    try_gpgme!(context.decrypt(cyphertext, &mut cleartext));
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.decrypt_with_flags(cyphertext.as_slice(), &mut cleartext, flags)));
 
-    decode_context_result!(cleartext, env)
+    match transform_decryption_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), helpers::to_binary(env, &cleartext)?, nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
-pub fn sign_with_mode<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, mode_arg: Term, data: String) -> NifResult<Term<'a>> {
+pub fn decrypt_and_verify<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, cyphertext: Binary<'a>) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+
+    let mut cleartext: Vec<u8> = Vec::new();
+
+    let (decryption_result, verification_result) = try_gpgme!(with_callbacks!(context_arc, context, context.decrypt_and_verify(cyphertext.as_slice(), &mut cleartext)));
+
+    let decryption = transform_decryption_result(env, decryption_result);
+    let verification = transform_verification_result(env, verification_result);
+
+    match (decryption, verification) {
+        (Ok(decryption), Ok(verification)) => Ok((atoms::ok(), helpers::to_binary(env, &cleartext)?, decryption, verification).encode(env)),
+        _ => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn sign_with_mode<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, mode_arg: Term, data: Binary<'a>) -> NifResult<Term<'a>> {
     unpack_mutable_context!(context, context_arc);
 
     let mode = sign_mode::arg_to_sign_mode(mode_arg)?;
 
     let mut signature: Vec<u8> = Vec::new();
 
-    try_gpgme!(context.sign(mode, data, &mut signature));
+    let result = match with_callbacks!(context_arc, context, context.sign(mode, data.as_slice(), &mut signature)) {
+        Ok(result) => result,
+        Err(err) => return encode_signing_error(env, &context, err)
+    };
 
-    decode_context_result!(signature, env)
+    match transform_signing_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), helpers::to_binary(env, &signature)?, nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+// Failed signing operations report the invalid signers along with the error.
+fn encode_signing_error<'a>(env: Env<'a>, context: &Context, err: gpgme::Error) -> NifResult<Term<'a>> {
+    let reason = XError::new(err);
+
+    match last_signing_result(context) {
+        Some(result) => {
+            if result.invalid_signers().count() == 0 {
+                return Err(Error::Term(Box::new(reason)));
+            }
+
+            match transform_signing_result(env, result) {
+                Ok(nif_result) => Ok((atoms::error(), reason, nif_result).encode(env)),
+                Err(_) => Err(Error::Term(Box::new(reason)))
+            }
+        },
+        None => Err(Error::Term(Box::new(reason)))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn verify_opaque<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, signature: Binary<'a>, data: Binary<'a>) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.verify_opaque(signature.as_slice(), data.as_slice())));
+
+    match transform_verification_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
 }
 
 #[rustler::nif(schedule = "DirtyIo")]
-pub fn verify_opaque(env: Env, context_arc: ResourceArc<resource::ContextNifResource>, signature: String, data: String) -> NifResult<Term> {
+pub fn verify_detached<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, signature: Binary<'a>, data: Binary<'a>) -> NifResult<Term<'a>> {
     unpack_mutable_context!(context, context_arc);
 
-    let result = try_gpgme!(context.verify_opaque(signature, data));
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.verify_detached(signature.as_slice(), data.as_slice())));
 
     match transform_verification_result(env, result) {
         Ok(nif_result) => Ok((atoms::ok(), nif_result).encode(env)),
-        Err(_) => Ok((atoms::error(), String::from("Could not decode cyphertext to utf8")).encode(env))
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+pub fn verify_clear<'a>(env: Env<'a>, context_arc: ResourceArc<resource::ContextNifResource>, signature: Binary<'a>) -> NifResult<Term<'a>> {
+    unpack_mutable_context!(context, context_arc);
+
+    let mut plaintext: Vec<u8> = Vec::new();
+
+    let result = try_gpgme!(with_callbacks!(context_arc, context, context.verify_clear(signature.as_slice(), &mut plaintext)));
+
+    match transform_verification_result(env, result) {
+        Ok(nif_result) => Ok((atoms::ok(), helpers::to_binary(env, &plaintext)?, nif_result).encode(env)),
+        Err(_) => Ok((atoms::error(), XError::decode("result")).encode(env))
     }
 }
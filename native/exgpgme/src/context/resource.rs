@@ -1,19 +1,95 @@
 use rustler::resource::ResourceArc;
 use std::sync::{Arc, RwLock};
+use gpgme;
 use gpgme::Context;
+use gpgme_sys as ffi;
+use context::callbacks::Callbacks;
+
+// gpgme can't list the flags of a context, these are the ones set_flag accepts.
+const CONTEXT_FLAGS: [&str; 14] = [
+    "redraw",
+    "full-status",
+    "raw-description",
+    "export-session-key",
+    "override-session-key",
+    "auto-key-retrieve",
+    "request-origin",
+    "no-symkey-cache",
+    "ignore-mdc-error",
+    "auto-key-locate",
+    "trust-model",
+    "extended-edit",
+    "cert-expire",
+    "key-origin"
+];
 
 pub struct ContextNifResource {
-    pub context: Arc<RwLock<Context>>
+    pub context: Arc<RwLock<Context>>,
+    pub callbacks: RwLock<Callbacks>,
+    // Operations hold the lock while running, cancelling has to reach the context without it.
+    pub raw: ffi::gpgme_ctx_t
 }
 unsafe impl Send for ContextNifResource {}
 unsafe impl Sync for ContextNifResource {}
 
+// Copy of the context for operations that run on their own thread without holding the lock.
+pub fn duplicate_context(context: &Context) -> gpgme::Result<Context> {
+    let mut duplicate = Context::from_protocol(context.protocol())?;
+
+    {
+        let engine_info = context.engine_info();
+
+        if let Ok(path) = engine_info.path() {
+            duplicate.set_engine_path(path)?;
+        }
+
+        if let Ok(home_dir) = engine_info.home_dir() {
+            duplicate.set_engine_home_dir(home_dir)?;
+        }
+    }
+
+    duplicate.set_armor(context.armor());
+    duplicate.set_text_mode(context.text_mode());
+    duplicate.set_offline(context.offline());
+    duplicate.set_pinentry_mode(context.pinentry_mode())?;
+    duplicate.set_key_list_mode(context.key_list_mode())?;
+
+    for signer in context.signers() {
+        duplicate.add_signer(&signer)?;
+    }
+
+    for name in CONTEXT_FLAGS.iter() {
+        if let Ok(value) = context.get_flag(*name) {
+            if !value.is_empty() {
+                duplicate.set_flag(*name, value)?;
+            }
+        }
+    }
+
+    Ok(duplicate)
+}
+
 pub fn wrap_context(context: Context) -> ResourceArc<ContextNifResource> {
+    let raw = context.as_raw();
+
     ResourceArc::new(ContextNifResource{
-        context: Arc::new(RwLock::new(context))
+        context: Arc::new(RwLock::new(context)),
+        callbacks: RwLock::new(Callbacks::default()),
+        raw: raw
     })
 }
 
+// gpgme_cancel_async is thread safe and makes the running operation fail with GPG_ERR_CANCELED.
+pub fn cancel(raw: ffi::gpgme_ctx_t) -> gpgme::Result<()> {
+    let err = unsafe { ffi::gpgme_cancel_async(raw) };
+
+    if err == 0 {
+        Ok(())
+    } else {
+        Err(gpgme::Error::new(err))
+    }
+}
+
 macro_rules! unpack_immutable_context {
     ($context:ident, $context_arc:expr) => (
         let $context = $context_arc.deref().context.read().unwrap();
@@ -0,0 +1,29 @@
+use rustler::{Error};
+use rustler::types::list::ListIterator;
+use gpgme;
+use gpgme::CreateKeyFlags;
+
+pub fn arg_to_create_key_flags(atoms: ListIterator) -> Result<CreateKeyFlags, Error> {
+    let mut flags = CreateKeyFlags::empty();
+
+    for atom in atoms {
+        let name = atom.atom_to_string()?;
+
+        flags.insert(string_to_flag(name)?);
+    }
+
+    Ok(flags)
+}
+
+pub fn string_to_flag(name: String) -> Result<CreateKeyFlags, Error> {
+    match name.as_ref() {
+      "sign" => Ok(gpgme::CreateKeyFlags::SIGN),
+      "encrypt" => Ok(gpgme::CreateKeyFlags::ENCR),
+      "cert" => Ok(gpgme::CreateKeyFlags::CERT),
+      "auth" => Ok(gpgme::CreateKeyFlags::AUTH),
+      "no_expire" => Ok(gpgme::CreateKeyFlags::NOEXPIRE),
+      "no_password" => Ok(gpgme::CreateKeyFlags::NOPASSWD),
+      "force" => Ok(gpgme::CreateKeyFlags::FORCE),
+      _ => Err(Error::BadArg)
+    }
+}
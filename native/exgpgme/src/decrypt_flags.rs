@@ -0,0 +1,24 @@
+use rustler::{Error};
+use rustler::types::list::ListIterator;
+use gpgme;
+use gpgme::DecryptFlags;
+
+pub fn arg_to_decrypt_flags(atoms: ListIterator) -> Result<DecryptFlags, Error> {
+    let mut flags = DecryptFlags::empty();
+
+    for atom in atoms {
+        let name = atom.atom_to_string()?;
+
+        flags.insert(string_to_flag(name)?);
+    }
+
+    Ok(flags)
+}
+
+pub fn string_to_flag(name: String) -> Result<DecryptFlags, Error> {
+    match name.as_ref() {
+      "verify" => Ok(gpgme::DecryptFlags::VERIFY),
+      "unwrap" => Ok(gpgme::DecryptFlags::UNWRAP),
+      _ => Err(Error::BadArg)
+    }
+}
@@ -0,0 +1,135 @@
+use rustler::{Atom, Encoder, Env, Term};
+use rustler::types::elixir_struct;
+use gpgme;
+
+mod atoms {
+    atoms! {
+        exception = "__exception__",
+        code,
+        source,
+        raw,
+        message,
+        no_data,
+        bad_passphrase,
+        no_pubkey,
+        no_seckey,
+        canceled,
+        unusable_pubkey,
+        unusable_seckey,
+        bad_signature,
+        eof,
+        not_found,
+        inv_value,
+        inv_name,
+        unknown_name,
+        ambiguous_name,
+        timeout,
+        general,
+        bad_data,
+        inv_state,
+        not_supported,
+        other,
+        unknown,
+        gcrypt,
+        gpg,
+        gpgsm,
+        gpg_agent,
+        pinentry,
+        scd,
+        gpgme,
+        keybox,
+        ksba,
+        dirmngr
+    }
+}
+
+// Layout of libgpg-error codes: the source lives in the bits above the error code.
+const SOURCE_SHIFT: u32 = 24;
+const SOURCE_MASK: u32 = 0x7F;
+
+pub struct XError {
+    error: gpgme::Error,
+    message: Option<String>
+}
+
+impl XError {
+    pub fn new(error: gpgme::Error) -> XError {
+        XError{error: error, message: None}
+    }
+
+    pub fn with_message(error: gpgme::Error, message: &str) -> XError {
+        XError{error: error, message: Some(String::from(message))}
+    }
+
+    pub fn decode(subject: &str) -> XError {
+        XError::with_message(gpgme::Error::BAD_DATA, &format!("Could not decode {} to utf8", subject))
+    }
+}
+
+impl From<gpgme::Error> for XError {
+    fn from(error: gpgme::Error) -> XError {
+        XError::new(error)
+    }
+}
+
+impl Encoder for XError {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        let message = match self.message {
+            Some(ref message) => message.clone(),
+            None => self.error.description().into_owned()
+        };
+
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Error").ok().unwrap()
+            .map_put(atoms::exception().encode(env), true.encode(env)).ok().unwrap()
+            .map_put(atoms::code().encode(env), transform_code(self.error).encode(env)).ok().unwrap()
+            .map_put(atoms::source().encode(env), transform_source(self.error).encode(env)).ok().unwrap()
+            .map_put(atoms::raw().encode(env), self.error.raw().encode(env)).ok().unwrap()
+            .map_put(atoms::message().encode(env), message.encode(env)).ok().unwrap()
+    }
+}
+
+fn transform_code(error: gpgme::Error) -> Atom {
+    let codes = [
+        (gpgme::Error::NO_DATA, atoms::no_data()),
+        (gpgme::Error::BAD_PASSPHRASE, atoms::bad_passphrase()),
+        (gpgme::Error::NO_PUBKEY, atoms::no_pubkey()),
+        (gpgme::Error::NO_SECKEY, atoms::no_seckey()),
+        (gpgme::Error::CANCELED, atoms::canceled()),
+        (gpgme::Error::UNUSABLE_PUBKEY, atoms::unusable_pubkey()),
+        (gpgme::Error::UNUSABLE_SECKEY, atoms::unusable_seckey()),
+        (gpgme::Error::BAD_SIGNATURE, atoms::bad_signature()),
+        (gpgme::Error::EOF, atoms::eof()),
+        (gpgme::Error::NOT_FOUND, atoms::not_found()),
+        (gpgme::Error::INV_VALUE, atoms::inv_value()),
+        (gpgme::Error::INV_NAME, atoms::inv_name()),
+        (gpgme::Error::UNKNOWN_NAME, atoms::unknown_name()),
+        (gpgme::Error::AMBIGUOUS_NAME, atoms::ambiguous_name()),
+        (gpgme::Error::TIMEOUT, atoms::timeout()),
+        (gpgme::Error::GENERAL, atoms::general()),
+        (gpgme::Error::BAD_DATA, atoms::bad_data()),
+        (gpgme::Error::INV_STATE, atoms::inv_state()),
+        (gpgme::Error::NOT_SUPPORTED, atoms::not_supported())
+    ];
+
+    codes.iter()
+        .find(| &&(known, _) | known.code() == error.code())
+        .map(| &(_, atom) | atom)
+        .unwrap_or_else(atoms::other)
+}
+
+fn transform_source(error: gpgme::Error) -> Atom {
+    match (error.raw() >> SOURCE_SHIFT) & SOURCE_MASK {
+        0 => atoms::unknown(),
+        1 => atoms::gcrypt(),
+        2 => atoms::gpg(),
+        3 => atoms::gpgsm(),
+        4 => atoms::gpg_agent(),
+        5 => atoms::pinentry(),
+        6 => atoms::scd(),
+        7 => atoms::gpgme(),
+        8 => atoms::keybox(),
+        9 => atoms::ksba(),
+        10 => atoms::dirmngr(),
+        _ => atoms::other()
+    }
+}
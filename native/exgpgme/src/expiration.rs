@@ -0,0 +1,63 @@
+use rustler::{Term, Error};
+use rustler::TermType;
+use rustler::types::tuple;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub enum ExpirationTarget {
+    Primary,
+    All,
+    Subkeys(Vec<String>)
+}
+
+pub fn arg_to_expiration(arg: Term) -> Result<Duration, Error> {
+    match arg.get_type() {
+        TermType::Atom => {
+            let name = arg.atom_to_string()?;
+            match name.as_ref() {
+                "never" => Ok(Duration::from_secs(0)),
+                _ => Err(Error::BadArg)
+            }
+        },
+        TermType::Integer => {
+            let seconds: u64 = arg.decode()?;
+            Ok(Duration::from_secs(seconds))
+        },
+        TermType::Tuple => {
+            let tuple = tuple::get_tuple(arg)?;
+            if tuple.len() != 2 {
+                return Err(Error::BadArg);
+            }
+
+            let name: String = tuple[0].atom_to_string()?;
+            let timestamp: u64 = tuple[1].decode()?;
+            if name != "at" {
+                return Err(Error::BadArg);
+            }
+
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("time");
+            match Duration::from_secs(timestamp).checked_sub(now) {
+                Some(duration) if duration.as_secs() > 0 => Ok(duration),
+                _ => Err(Error::BadArg)
+            }
+        },
+        _ => Err(Error::BadArg)
+    }
+}
+
+pub fn arg_to_expiration_target(arg: Term) -> Result<ExpirationTarget, Error> {
+    match arg.get_type() {
+        TermType::Atom => {
+            let name = arg.atom_to_string()?;
+            match name.as_ref() {
+                "primary" => Ok(ExpirationTarget::Primary),
+                "all" => Ok(ExpirationTarget::All),
+                _ => Err(Error::BadArg)
+            }
+        },
+        TermType::List => {
+            let fingerprints: Vec<String> = arg.decode()?;
+            Ok(ExpirationTarget::Subkeys(fingerprints))
+        },
+        _ => Err(Error::BadArg)
+    }
+}
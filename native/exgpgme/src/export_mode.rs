@@ -0,0 +1,28 @@
+use rustler::{Error};
+use rustler::types::list::ListIterator;
+use gpgme;
+use gpgme::ExportMode;
+
+pub fn arg_to_export_mode(atoms: ListIterator) -> Result<ExportMode, Error> {
+    let mut mode = ExportMode::empty();
+
+    for atom in atoms {
+        let name = atom.atom_to_string()?;
+
+        mode.insert(string_to_mode(name)?);
+    }
+
+    Ok(mode)
+}
+
+pub fn string_to_mode(name: String) -> Result<ExportMode, Error> {
+    match name.as_ref() {
+      "extern" => Ok(gpgme::ExportMode::EXTERN),
+      "minimal" => Ok(gpgme::ExportMode::MINIMAL),
+      "secret" => Ok(gpgme::ExportMode::SECRET),
+      "raw" => Ok(gpgme::ExportMode::RAW),
+      "pkcs12" => Ok(gpgme::ExportMode::PKCS12),
+      "ssh" => Ok(gpgme::ExportMode::SSH),
+      _ => Err(Error::BadArg)
+    }
+}
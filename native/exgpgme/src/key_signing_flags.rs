@@ -0,0 +1,25 @@
+use rustler::{Error};
+use rustler::types::list::ListIterator;
+use gpgme;
+use gpgme::KeySigningFlags;
+
+pub fn arg_to_key_signing_flags(atoms: ListIterator) -> Result<KeySigningFlags, Error> {
+    let mut flags = KeySigningFlags::empty();
+
+    for atom in atoms {
+        let name = atom.atom_to_string()?;
+
+        flags.insert(string_to_flag(name)?);
+    }
+
+    Ok(flags)
+}
+
+pub fn string_to_flag(name: String) -> Result<KeySigningFlags, Error> {
+    match name.as_ref() {
+      "local" => Ok(gpgme::KeySigningFlags::LOCAL),
+      "no_expire" => Ok(gpgme::KeySigningFlags::NOEXPIRE),
+      "force" => Ok(gpgme::KeySigningFlags::FORCE),
+      _ => Err(Error::BadArg)
+    }
+}
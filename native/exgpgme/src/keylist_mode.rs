@@ -0,0 +1,68 @@
+use rustler::{Env, Term, Encoder, Error, Atom};
+use rustler::types::list::ListIterator;
+use gpgme;
+use gpgme::KeyListMode;
+
+mod atoms {
+    atoms! {
+        local,
+        extern_ = "extern",
+        sigs,
+        sig_notations,
+        with_secret,
+        with_tofu,
+        ephemeral,
+        validate
+    }
+}
+
+pub struct XKeyListMode(pub KeyListMode);
+
+impl Encoder for XKeyListMode {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        let modes: Vec<(KeyListMode, Atom)> = vec![
+            (gpgme::KeyListMode::LOCAL, atoms::local()),
+            (gpgme::KeyListMode::EXTERN, atoms::extern_()),
+            (gpgme::KeyListMode::SIGS, atoms::sigs()),
+            (gpgme::KeyListMode::SIG_NOTATIONS, atoms::sig_notations()),
+            (gpgme::KeyListMode::WITH_SECRET, atoms::with_secret()),
+            (gpgme::KeyListMode::WITH_TOFU, atoms::with_tofu()),
+            (gpgme::KeyListMode::EPHEMERAL, atoms::ephemeral()),
+            (gpgme::KeyListMode::VALIDATE, atoms::validate())
+        ];
+
+        modes
+            .into_iter()
+            .filter(| &(mode, _) | self.0.contains(mode))
+            .map(| (_, atom) | atom)
+            .collect::<Vec<Atom>>()
+            .encode(env)
+    }
+}
+
+pub fn arg_to_keylist_mode(atoms: ListIterator) -> Result<KeyListMode, Error> {
+    let mut mode = KeyListMode::empty();
+
+    for atom in atoms {
+        let name = atom.atom_to_string()?;
+
+        mode.insert(string_to_mode(name)?);
+    }
+
+    Ok(mode)
+}
+
+pub fn string_to_mode(name: String) -> Result<KeyListMode, Error> {
+    match name.as_ref() {
+      "local" => Ok(gpgme::KeyListMode::LOCAL),
+      "extern" => Ok(gpgme::KeyListMode::EXTERN),
+      "sigs" => Ok(gpgme::KeyListMode::SIGS),
+      "sig_notations" => Ok(gpgme::KeyListMode::SIG_NOTATIONS),
+      "with_secret" => Ok(gpgme::KeyListMode::WITH_SECRET),
+      "with_tofu" => Ok(gpgme::KeyListMode::WITH_TOFU),
+      "ephemeral" => Ok(gpgme::KeyListMode::EPHEMERAL),
+      "validate" => Ok(gpgme::KeyListMode::VALIDATE),
+      "locate" => Ok(gpgme::KeyListMode::LOCATE),
+      _ => Err(Error::BadArg)
+    }
+}
@@ -0,0 +1,60 @@
+use rustler::resource::ResourceArc;
+use gpgme;
+use gpgme::Context;
+use gpgme::keys::Key;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread;
+use cancel::CancelHandle;
+
+const CURSOR_BUFFER_SIZE: usize = 100;
+
+pub struct KeyCursorResource {
+    pub receiver: Mutex<Option<Receiver<Result<Key, gpgme::Error>>>>,
+    // Error that ended the previous batch early, it is reported by the next call.
+    pub error: Mutex<Option<gpgme::Error>>,
+    pub cancel: Arc<CancelHandle>
+}
+
+// The bounded channel keeps the listing thread at most CURSOR_BUFFER_SIZE keys ahead of the reader.
+pub fn spawn_key_cursor(mut context: Context, patterns: Vec<String>, secret_only: bool) -> ResourceArc<KeyCursorResource> {
+    let (sender, receiver) = sync_channel(CURSOR_BUFFER_SIZE);
+    let cancel = Arc::new(CancelHandle::running(context.as_raw()));
+    let thread_cancel = cancel.clone();
+
+    thread::spawn(move || {
+        list_keys(&mut context, &thread_cancel, patterns, secret_only, &sender);
+
+        if let Err(err) = thread_cancel.finish(Ok(())) {
+            let _ = sender.send(Err(err));
+        }
+    });
+
+    ResourceArc::new(KeyCursorResource{
+        receiver: Mutex::new(Some(receiver)),
+        error: Mutex::new(None),
+        cancel: cancel
+    })
+}
+
+// A canceled listing stops before the next key, the cancel itself is reported once the listing is done.
+fn list_keys(context: &mut Context, cancel: &CancelHandle, patterns: Vec<String>, secret_only: bool, sender: &SyncSender<Result<Key, gpgme::Error>>) {
+    let key_list = if secret_only {
+        context.find_secret_keys(patterns)
+    } else {
+        context.find_keys(patterns)
+    };
+
+    match key_list {
+        Ok(key_list) => for key in key_list {
+            if cancel.is_requested() || sender.send(key).is_err() {
+                break;
+            }
+        },
+        Err(err) => {
+            if !cancel.is_requested() {
+                let _ = sender.send(Err(err));
+            }
+        }
+    }
+}
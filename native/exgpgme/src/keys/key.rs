@@ -0,0 +1,72 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::keys::Key;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+use protocol::XProtocol;
+use validity::transform_validity;
+
+mod atoms {
+    atoms! {
+        fingerprint,
+        id,
+        protocol,
+        owner_trust,
+        is_revoked,
+        is_expired,
+        is_disabled,
+        is_invalid,
+        can_encrypt,
+        can_sign,
+        can_certify,
+        can_authenticate,
+        has_secret,
+        issuer_serial,
+        issuer_name,
+        chain_id
+    }
+}
+
+pub fn transform_key<'a>(env: Env<'a>, key: &Key) -> Result<Term<'a>, Utf8Error> {
+    let fingerprint_atom = atoms::fingerprint().encode(env);
+    let id_atom = atoms::id().encode(env);
+    let protocol_atom = atoms::protocol().encode(env);
+    let owner_trust_atom = atoms::owner_trust().encode(env);
+    let is_revoked_atom = atoms::is_revoked().encode(env);
+    let is_expired_atom = atoms::is_expired().encode(env);
+    let is_disabled_atom = atoms::is_disabled().encode(env);
+    let is_invalid_atom = atoms::is_invalid().encode(env);
+    let can_encrypt_atom = atoms::can_encrypt().encode(env);
+    let can_sign_atom = atoms::can_sign().encode(env);
+    let can_certify_atom = atoms::can_certify().encode(env);
+    let can_authenticate_atom = atoms::can_authenticate().encode(env);
+    let has_secret_atom = atoms::has_secret().encode(env);
+    let issuer_serial_atom = atoms::issuer_serial().encode(env);
+    let issuer_name_atom = atoms::issuer_name().encode(env);
+    let chain_id_atom = atoms::chain_id().encode(env);
+
+    let fingerprint = string_or_null!(key.fingerprint(), env)?;
+    let id = string_or_null!(key.id(), env)?;
+    let issuer_serial = string_or_null!(key.issuer_serial(), env)?;
+    let issuer_name = string_or_null!(key.issuer_name(), env)?;
+    let chain_id = string_or_null!(key.chain_id(), env)?;
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Keys.Key").ok().unwrap()
+            .map_put(fingerprint_atom, fingerprint).ok().unwrap()
+            .map_put(id_atom, id).ok().unwrap()
+            .map_put(protocol_atom, XProtocol(key.protocol()).encode(env)).ok().unwrap()
+            .map_put(owner_trust_atom, transform_validity(key.owner_trust()).encode(env)).ok().unwrap()
+            .map_put(is_revoked_atom, key.is_revoked().encode(env)).ok().unwrap()
+            .map_put(is_expired_atom, key.is_expired().encode(env)).ok().unwrap()
+            .map_put(is_disabled_atom, key.is_disabled().encode(env)).ok().unwrap()
+            .map_put(is_invalid_atom, key.is_invalid().encode(env)).ok().unwrap()
+            .map_put(can_encrypt_atom, key.can_encrypt().encode(env)).ok().unwrap()
+            .map_put(can_sign_atom, key.can_sign().encode(env)).ok().unwrap()
+            .map_put(can_certify_atom, key.can_certify().encode(env)).ok().unwrap()
+            .map_put(can_authenticate_atom, key.can_authenticate().encode(env)).ok().unwrap()
+            .map_put(has_secret_atom, key.has_secret().encode(env)).ok().unwrap()
+            .map_put(issuer_serial_atom, issuer_serial).ok().unwrap()
+            .map_put(issuer_name_atom, issuer_name).ok().unwrap()
+            .map_put(chain_id_atom, chain_id).ok().unwrap()
+    )
+}
@@ -0,0 +1,34 @@
+use rustler::Atom;
+use gpgme::KeyOrigin;
+
+mod atoms {
+    atoms! {
+        unknown,
+        key_server,
+        dane,
+        wkd,
+        url,
+        file,
+        self_ = "self",
+        other
+    }
+}
+
+#[derive(NifUntaggedEnum)]
+pub enum KeyOriginResult {
+    Atom(Atom),
+    Tuple((Atom, u32))
+}
+
+pub fn transform_key_origin(origin: KeyOrigin) -> KeyOriginResult {
+    match origin {
+        KeyOrigin::Unknown => KeyOriginResult::Atom(atoms::unknown()),
+        KeyOrigin::KeyServer => KeyOriginResult::Atom(atoms::key_server()),
+        KeyOrigin::Dane => KeyOriginResult::Atom(atoms::dane()),
+        KeyOrigin::Wkd => KeyOriginResult::Atom(atoms::wkd()),
+        KeyOrigin::Url => KeyOriginResult::Atom(atoms::url()),
+        KeyOrigin::File => KeyOriginResult::Atom(atoms::file()),
+        KeyOrigin::Self_ => KeyOriginResult::Atom(atoms::self_()),
+        KeyOrigin::Other(other) => KeyOriginResult::Tuple((atoms::other(), other)),
+    }
+}
@@ -0,0 +1,95 @@
+use rustler::{Encoder, Env, Error, NifResult, Term};
+use rustler::resource::ResourceArc;
+use gpgme::keys::Key;
+use std::str::Utf8Error;
+use error::XError;
+
+pub mod key;
+pub mod key_origin;
+pub mod tofu_policy;
+pub mod tofu_info;
+pub mod user_id;
+pub mod subkey;
+pub mod cursor;
+
+mod atoms {
+    atoms! {
+        ok,
+        error
+    }
+}
+
+pub struct KeyResource {
+    pub key: Key
+}
+
+pub fn wrap_key(key: Key) -> ResourceArc<KeyResource> {
+    ResourceArc::new(KeyResource{
+        key
+    })
+}
+
+pub fn keys_not_empty(key_length: usize) -> Result<(), Error> {
+    if key_length < 1 {
+        Err(Error::BadArg)
+    } else {
+        Ok(())
+    }
+}
+
+#[rustler::nif]
+pub fn key_info(env: Env, key_arc: ResourceArc<KeyResource>) -> NifResult<Term> {
+    Ok(
+        match key::transform_key(env, &key_arc.key) {
+            Ok(result) => (atoms::ok(), result).encode(env),
+            Err(_) => (atoms::error(), XError::decode("key")).encode(env)
+        }
+    )
+}
+
+#[rustler::nif]
+pub fn user_ids(env: Env, key_arc: ResourceArc<KeyResource>) -> NifResult<Term> {
+    let user_ids: Result<Vec<Term>, Utf8Error> = key_arc.key.user_ids()
+        .map(| user_id | {
+            user_id::transform_user_id(env, user_id)
+        })
+        .collect();
+
+    Ok(
+        match user_ids {
+            Ok(result) => (atoms::ok(), result).encode(env),
+            Err(_) => (atoms::error(), XError::decode("user id")).encode(env)
+        }
+    )
+}
+
+#[rustler::nif]
+pub fn subkeys(env: Env, key_arc: ResourceArc<KeyResource>) -> NifResult<Term> {
+    let subkeys: Result<Vec<Term>, Utf8Error> = key_arc.key.subkeys()
+        .map(| subkey | {
+            subkey::transform_subkey(env, subkey)
+        })
+        .collect();
+
+    Ok(
+        match subkeys {
+            Ok(result) => (atoms::ok(), result).encode(env),
+            Err(_) => (atoms::error(), XError::decode("subkey")).encode(env)
+        }
+    )
+}
+
+#[macro_export]
+macro_rules! unpack_key_list {
+    ($keys:ident, $arg:expr) => (
+        let keys_with_errors: Vec<ResourceArc<keys::KeyResource>> = $arg
+            .decode::<ListIterator>()?
+            .map(| key_arg | { key_arg.decode::<ResourceArc<keys::KeyResource>>() })
+            .collect::<NifResult<Vec<ResourceArc<keys::KeyResource>>>>()?;
+
+        let $keys: Vec<&Key> = keys_with_errors
+            .iter()
+            .map(| ref key_arc | &key_arc.key)
+            .collect();
+    );
+}
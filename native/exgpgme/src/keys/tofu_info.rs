@@ -0,0 +1,51 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::keys::TofuInfo;
+use std::time::UNIX_EPOCH;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+use keys::tofu_policy::transform_tofu_policy;
+
+mod atoms {
+    atoms! {
+        validity,
+        policy,
+        signature_count,
+        encrypted_count,
+        first_signed,
+        last_signed,
+        first_encrypted,
+        last_encrypted,
+        description
+    }
+}
+
+pub fn transform_tofu_info<'a>(env: Env<'a>, info: TofuInfo) -> Result<Term<'a>, Utf8Error> {
+    let validity_atom = atoms::validity().encode(env);
+    let policy_atom = atoms::policy().encode(env);
+    let signature_count_atom = atoms::signature_count().encode(env);
+    let encrypted_count_atom = atoms::encrypted_count().encode(env);
+    let first_signed_atom = atoms::first_signed().encode(env);
+    let last_signed_atom = atoms::last_signed().encode(env);
+    let first_encrypted_atom = atoms::first_encrypted().encode(env);
+    let last_encrypted_atom = atoms::last_encrypted().encode(env);
+    let description_atom = atoms::description().encode(env);
+
+    let first_signed = nif_or_nil!(info.first_signed(), env, content, { content.duration_since(UNIX_EPOCH).expect("time").as_secs() });
+    let last_signed = nif_or_nil!(info.last_signed(), env, content, { content.duration_since(UNIX_EPOCH).expect("time").as_secs() });
+    let first_encrypted = nif_or_nil!(info.first_encrypted(), env, content, { content.duration_since(UNIX_EPOCH).expect("time").as_secs() });
+    let last_encrypted = nif_or_nil!(info.last_encrypted(), env, content, { content.duration_since(UNIX_EPOCH).expect("time").as_secs() });
+    let description = string_or_null!(info.description(), env)?;
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Keys.TofuInfo").ok().unwrap()
+            .map_put(validity_atom, info.validity().encode(env)).ok().unwrap()
+            .map_put(policy_atom, transform_tofu_policy(info.policy()).encode(env)).ok().unwrap()
+            .map_put(signature_count_atom, info.signature_count().encode(env)).ok().unwrap()
+            .map_put(encrypted_count_atom, info.encrypted_count().encode(env)).ok().unwrap()
+            .map_put(first_signed_atom, first_signed).ok().unwrap()
+            .map_put(last_signed_atom, last_signed).ok().unwrap()
+            .map_put(first_encrypted_atom, first_encrypted).ok().unwrap()
+            .map_put(last_encrypted_atom, last_encrypted).ok().unwrap()
+            .map_put(description_atom, description).ok().unwrap()
+    )
+}
@@ -0,0 +1,32 @@
+use rustler::Atom;
+use gpgme::TofuPolicy;
+
+mod atoms {
+    atoms! {
+        none,
+        auto,
+        good,
+        unknown,
+        bad,
+        ask,
+        other
+    }
+}
+
+#[derive(NifUntaggedEnum)]
+pub enum TofuPolicyResult {
+    Atom(Atom),
+    Tuple((Atom, u32))
+}
+
+pub fn transform_tofu_policy(policy: TofuPolicy) -> TofuPolicyResult {
+    match policy {
+        TofuPolicy::None => TofuPolicyResult::Atom(atoms::none()),
+        TofuPolicy::Auto => TofuPolicyResult::Atom(atoms::auto()),
+        TofuPolicy::Good => TofuPolicyResult::Atom(atoms::good()),
+        TofuPolicy::Unknown => TofuPolicyResult::Atom(atoms::unknown()),
+        TofuPolicy::Bad => TofuPolicyResult::Atom(atoms::bad()),
+        TofuPolicy::Ask => TofuPolicyResult::Atom(atoms::ask()),
+        TofuPolicy::Other(other) => TofuPolicyResult::Tuple((atoms::other(), other)),
+    }
+}
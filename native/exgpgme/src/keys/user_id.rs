@@ -0,0 +1,61 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::keys::UserId;
+use std::time::UNIX_EPOCH;
+use rustler::types::elixir_struct;
+use rustler::types::atom::nil;
+use std::str::Utf8Error;
+use validity::transform_validity;
+use keys::key_origin::transform_key_origin;
+use keys::tofu_info::transform_tofu_info;
+
+mod atoms {
+    atoms! {
+        uid,
+        name,
+        email,
+        comment,
+        validity,
+        is_revoked,
+        is_invalid,
+        origin,
+        last_update,
+        tofu_info
+    }
+}
+
+pub fn transform_user_id<'a>(env: Env<'a>, user_id: UserId) -> Result<Term<'a>, Utf8Error> {
+    let uid_atom = atoms::uid().encode(env);
+    let name_atom = atoms::name().encode(env);
+    let email_atom = atoms::email().encode(env);
+    let comment_atom = atoms::comment().encode(env);
+    let validity_atom = atoms::validity().encode(env);
+    let is_revoked_atom = atoms::is_revoked().encode(env);
+    let is_invalid_atom = atoms::is_invalid().encode(env);
+    let origin_atom = atoms::origin().encode(env);
+    let last_update_atom = atoms::last_update().encode(env);
+    let tofu_info_atom = atoms::tofu_info().encode(env);
+
+    let uid = string_or_null!(user_id.id(), env)?;
+    let name = string_or_null!(user_id.name(), env)?;
+    let email = string_or_null!(user_id.email(), env)?;
+    let comment = string_or_null!(user_id.comment(), env)?;
+    let last_update = user_id.last_update().duration_since(UNIX_EPOCH).expect("time").as_secs();
+    let tofu_info = match user_id.tofu_info() {
+        Some(info) => transform_tofu_info(env, info)?,
+        None => nil().encode(env)
+    };
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Keys.UserId").ok().unwrap()
+            .map_put(uid_atom, uid).ok().unwrap()
+            .map_put(name_atom, name).ok().unwrap()
+            .map_put(email_atom, email).ok().unwrap()
+            .map_put(comment_atom, comment).ok().unwrap()
+            .map_put(validity_atom, transform_validity(user_id.validity()).encode(env)).ok().unwrap()
+            .map_put(is_revoked_atom, user_id.is_revoked().encode(env)).ok().unwrap()
+            .map_put(is_invalid_atom, user_id.is_invalid().encode(env)).ok().unwrap()
+            .map_put(origin_atom, transform_key_origin(user_id.origin()).encode(env)).ok().unwrap()
+            .map_put(last_update_atom, last_update.encode(env)).ok().unwrap()
+            .map_put(tofu_info_atom, tofu_info).ok().unwrap()
+    )
+}
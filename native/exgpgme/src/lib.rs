@@ -1,15 +1,27 @@
 #[macro_use] extern crate rustler;
+#[macro_use] extern crate lazy_static;
 extern crate gpgme;
+extern crate gpgme_sys;
 
 use rustler::{Env, Term};
 
 #[macro_use] mod helpers;
 #[macro_use] mod keys;
+mod error;
 mod context;
 mod results;
 mod engine;
 mod protocol;
 mod encrypt_flags;
+mod decrypt_flags;
+mod keylist_mode;
+mod create_key_flags;
+mod expiration;
+mod key_signing_flags;
+mod export_mode;
+mod session;
+mod operation;
+mod cancel;
 mod pinentry_mode;
 mod sign_mode;
 mod validity;
@@ -35,15 +47,56 @@ rustler::init!(
        context::set_engine_home_dir,
        context::get_pinentry_mode,
        context::set_pinentry_mode,
+       context::get_keylist_mode,
+       context::set_keylist_mode,
+       context::add_signer,
+       context::clear_signers,
+       context::signers,
+       context::set_passphrase_provider,
+       context::clear_passphrase_provider,
+       context::reply_passphrase,
+       context::set_progress_handler,
+       context::clear_progress_handler,
+       context::set_status_handler,
+       context::clear_status_handler,
        context::import,
+       context::export_keys,
        context::find_key,
+       context::list_keys,
+       context::open_key_cursor,
+       context::next_keys,
+       context::close_key_cursor,
+       context::create_key,
+       context::create_subkey,
+       context::add_uid,
+       context::revoke_uid,
+       context::set_uid_flag,
+       context::set_key_expire,
+       context::sign_key,
+       context::revoke_signature,
        context::delete_key,
        context::delete_secret_key,
        context::decrypt,
+       context::decrypt_with_flags,
+       context::decrypt_and_verify,
        context::encrypt_with_flags,
        context::sign_and_encrypt_with_flags,
+       context::encrypt_file,
+       context::decrypt_file,
+       context::sign_file,
+       context::verify_file,
+       context::start_operation,
+       context::cancel,
+       context::open_session,
+       context::write_session,
+       context::finish_session,
        context::sign_with_mode,
-       context::verify_opaque
+       context::verify_opaque,
+       context::verify_detached,
+       context::verify_clear,
+       keys::key_info,
+       keys::user_ids,
+       keys::subkeys
     ],
     load = on_load
 );
@@ -51,5 +104,9 @@ rustler::init!(
 fn on_load<'a>(env: Env<'a>, _load_info: Term<'a>) -> bool {
     rustler::resource!(context::resource::ContextNifResource, env);
     rustler::resource!(keys::KeyResource, env);
+    rustler::resource!(keys::cursor::KeyCursorResource, env);
+    rustler::resource!(session::SessionResource, env);
+    rustler::resource!(operation::OperationResource, env);
+    rustler::resource!(context::callbacks::PassphraseRequestResource, env);
     true
 }
@@ -0,0 +1,118 @@
+use rustler::{Encoder, Env, Term};
+use rustler::env::OwnedEnv;
+use rustler::resource::ResourceArc;
+use rustler::types::LocalPid;
+use gpgme;
+use gpgme::{Context, Data};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use context::helpers;
+use context::callbacks;
+use context::callbacks::{Callbacks, Messenger, Origin};
+use cancel::{CancelHandle, CancelableReader};
+use error::XError;
+use session;
+use session::{Operation, OperationResult};
+
+// Background operations share a fixed number of threads, further operations wait for a free one.
+const OPERATION_WORKERS: usize = 8;
+
+mod atoms {
+    atoms! {
+        ok,
+        error,
+        gpgme_result
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+lazy_static! {
+    static ref WORKERS: Mutex<Sender<Job>> = {
+        let (sender, receiver) = channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for _ in 0..OPERATION_WORKERS {
+            let receiver = receiver.clone();
+            thread::spawn(move || work(&receiver));
+        }
+
+        Mutex::new(sender)
+    };
+}
+
+fn work(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock().unwrap().recv() {
+            Ok(job) => job,
+            Err(_) => return
+        };
+
+        job();
+    }
+}
+
+pub struct OperationResource {
+    pub cancel: Arc<CancelHandle>
+}
+
+// The operation runs on its own copy of the context, so the context stays usable and its lock is never held.
+pub fn spawn_operation(pid: LocalPid, mut context: Context, callbacks: Callbacks, operation: Operation, input: Vec<u8>) -> ResourceArc<OperationResource> {
+    let operation_arc = ResourceArc::new(OperationResource{
+        cancel: Arc::new(CancelHandle::pending())
+    });
+    let reply_arc = operation_arc.clone();
+
+    let job: Job = Box::new(move || {
+        let mut output: Vec<u8> = Vec::new();
+        let origin = Origin::Operation(reply_arc.clone());
+        let result = run(&reply_arc.cancel, &origin, &mut context, &callbacks, operation, &input, &mut output);
+
+        OwnedEnv::new().send_and_clear(&pid, | env | {
+            (atoms::gpgme_result(), reply_arc, encode_result(env, result, &output)).encode(env)
+        });
+    });
+
+    let _ = WORKERS.lock().unwrap().send(job);
+
+    operation_arc
+}
+
+fn run(cancel: &Arc<CancelHandle>, origin: &Origin, context: &mut Context, callbacks: &Callbacks, operation: Operation, input: &[u8], output: &mut Vec<u8>) -> gpgme::Result<OperationResult> {
+    // Operations canceled while waiting for a worker never start.
+    if !cancel.start(context.as_raw()) {
+        return Err(gpgme::Error::CANCELED);
+    }
+
+    let result = execute(cancel, origin, context, callbacks, operation, input, output);
+
+    cancel.finish(result)
+}
+
+fn execute(cancel: &Arc<CancelHandle>, origin: &Origin, context: &mut Context, callbacks: &Callbacks, operation: Operation, input: &[u8], output: &mut Vec<u8>) -> gpgme::Result<OperationResult> {
+    let reader = CancelableReader{reader: input, handle: cancel.clone()};
+    let mut input = Data::from_reader(reader).map_err(| err | err.error())?;
+    let mut output = Data::from_writer(output).map_err(| err | err.error())?;
+
+    callbacks::with_callbacks(Messenger::Thread, origin, callbacks, context, | context | {
+        session::run_operation(context, operation, &mut input, &mut output)
+    })
+}
+
+fn encode_result<'a>(env: Env<'a>, result: gpgme::Result<OperationResult>, output: &[u8]) -> Term<'a> {
+    let result = match result {
+        Ok(result) => result,
+        Err(err) => return (atoms::error(), XError::new(err)).encode(env)
+    };
+
+    let output_binary = match helpers::to_binary(env, output) {
+        Ok(binary) => binary,
+        Err(_) => return (atoms::error(), XError::new(gpgme::Error::ENOMEM)).encode(env)
+    };
+
+    match session::transform_operation_result(env, result) {
+        Ok(nif_result) => (atoms::ok(), output_binary, nif_result).encode(env),
+        Err(_) => (atoms::error(), XError::decode("result")).encode(env)
+    }
+}
@@ -0,0 +1,57 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::results::DecryptionResult;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+
+use ::results::recipient::transform_recipient;
+
+mod atoms {
+    atoms! {
+        unsupported_algorithm,
+        is_wrong_key_usage,
+        is_de_vs,
+        is_mime,
+        legacy_cipher_no_mdc,
+        filename,
+        symmetric_key_algorithm,
+        session_key,
+        recipients
+    }
+}
+
+pub fn transform_decryption_result(env: Env, decryption_result: DecryptionResult) -> Result<Term, Utf8Error> {
+    let unsupported_algorithm_atom = atoms::unsupported_algorithm().encode(env);
+    let is_wrong_key_usage_atom = atoms::is_wrong_key_usage().encode(env);
+    let is_de_vs_atom = atoms::is_de_vs().encode(env);
+    let is_mime_atom = atoms::is_mime().encode(env);
+    let legacy_cipher_no_mdc_atom = atoms::legacy_cipher_no_mdc().encode(env);
+    let filename_atom = atoms::filename().encode(env);
+    let symmetric_key_algorithm_atom = atoms::symmetric_key_algorithm().encode(env);
+    let session_key_atom = atoms::session_key().encode(env);
+    let recipients_atom = atoms::recipients().encode(env);
+
+    let unsupported_algorithm = string_or_null!(decryption_result.unsupported_algorithm(), env)?;
+    let filename = string_or_null!(decryption_result.filename(), env)?;
+    let symmetric_key_algorithm = string_or_null!(decryption_result.symmetric_key_algorithm(), env)?;
+    let session_key = string_or_null!(decryption_result.session_key(), env)?;
+
+    let recipients: Term = decryption_result.recipients()
+        .map(| recipient | {
+            transform_recipient(env, recipient)
+        })
+        .collect::<Result<Vec<Term>, Utf8Error>>()?
+        .encode(env);
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.DecryptionResult").ok().unwrap()
+            .map_put(unsupported_algorithm_atom, unsupported_algorithm).ok().unwrap()
+            .map_put(is_wrong_key_usage_atom, decryption_result.is_wrong_key_usage().encode(env)).ok().unwrap()
+            .map_put(is_de_vs_atom, decryption_result.is_de_vs().encode(env)).ok().unwrap()
+            .map_put(is_mime_atom, decryption_result.is_mime().encode(env)).ok().unwrap()
+            .map_put(legacy_cipher_no_mdc_atom, decryption_result.is_legacy_cipher_no_mdc().encode(env)).ok().unwrap()
+            .map_put(filename_atom, filename).ok().unwrap()
+            .map_put(symmetric_key_algorithm_atom, symmetric_key_algorithm).ok().unwrap()
+            .map_put(session_key_atom, session_key).ok().unwrap()
+            .map_put(recipients_atom, recipients).ok().unwrap()
+    )
+}
@@ -0,0 +1,44 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::Context;
+use gpgme::results::EncryptionResult;
+use gpgme_sys as ffi;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+
+use ::results::invalid_key::transform_invalid_key;
+
+mod atoms {
+    atoms! {
+        invalid_recipients
+    }
+}
+
+pub fn transform_encryption_result(env: Env, encryption_result: EncryptionResult) -> Result<Term, Utf8Error> {
+    let invalid_recipients_atom = atoms::invalid_recipients().encode(env);
+
+    let invalid_recipients: Term = encryption_result.invalid_recipients()
+        .map(| invalid_key | {
+            transform_invalid_key(env, invalid_key)
+        })
+        .collect::<Result<Vec<Term>, Utf8Error>>()?
+        .encode(env);
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.EncryptionResult").ok().unwrap()
+            .map_put(invalid_recipients_atom, invalid_recipients).ok().unwrap()
+    )
+}
+
+// gpgme-rs drops the result of a failed operation, but gpgme keeps it on the context.
+pub fn last_encryption_result(context: &Context) -> Option<EncryptionResult> {
+    unsafe {
+        let raw = ffi::gpgme_op_encrypt_result(context.as_raw());
+
+        if raw.is_null() {
+            None
+        } else {
+            ffi::gpgme_result_ref(raw as *mut _);
+            Some(EncryptionResult::from_raw(raw))
+        }
+    }
+}
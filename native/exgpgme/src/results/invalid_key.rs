@@ -0,0 +1,26 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::results::InvalidKey;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+use error::XError;
+
+mod atoms {
+    atoms! {
+        fingerprint,
+        reason
+    }
+}
+
+pub fn transform_invalid_key<'a>(env: Env<'a>, invalid_key: InvalidKey) -> Result<Term<'a>, Utf8Error> {
+    let fingerprint_atom = atoms::fingerprint().encode(env);
+    let reason_atom = atoms::reason().encode(env);
+
+    let fingerprint = string_or_null!(invalid_key.fingerprint(), env)?;
+    let reason = nif_or_nil!(invalid_key.reason(), env, error, { XError::new(error) });
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.InvalidKey").ok().unwrap()
+            .map_put(fingerprint_atom, fingerprint).ok().unwrap()
+            .map_put(reason_atom, reason).ok().unwrap()
+    )
+}
@@ -0,0 +1,30 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::results::KeyGenerationResult;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+
+mod atoms {
+    atoms! {
+        fingerprint,
+        primary_created,
+        sub_created,
+        uid_created
+    }
+}
+
+pub fn transform_key_generation_result<'a>(env: Env<'a>, result: KeyGenerationResult) -> Result<Term<'a>, Utf8Error> {
+    let fingerprint_atom = atoms::fingerprint().encode(env);
+    let primary_created_atom = atoms::primary_created().encode(env);
+    let sub_created_atom = atoms::sub_created().encode(env);
+    let uid_created_atom = atoms::uid_created().encode(env);
+
+    let fingerprint = string_or_null!(result.fingerprint(), env)?;
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.KeyGenerationResult").ok().unwrap()
+            .map_put(fingerprint_atom, fingerprint).ok().unwrap()
+            .map_put(primary_created_atom, result.has_primary_key().encode(env)).ok().unwrap()
+            .map_put(sub_created_atom, result.has_sub_key().encode(env)).ok().unwrap()
+            .map_put(uid_created_atom, result.has_uid().encode(env)).ok().unwrap()
+    )
+}
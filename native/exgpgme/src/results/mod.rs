@@ -3,3 +3,10 @@ pub mod signature;
 pub mod verification_result;
 pub mod import;
 pub mod import_result;
+pub mod key_generation_result;
+pub mod recipient;
+pub mod decryption_result;
+pub mod invalid_key;
+pub mod encryption_result;
+pub mod new_signature;
+pub mod signing_result;
@@ -0,0 +1,41 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::results::NewSignature;
+use std::time::UNIX_EPOCH;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+use key_algorithm::transform_key_algorithm;
+use hash_algorithm::transform_hash_algorithm;
+use sign_mode::XSignMode;
+
+mod atoms {
+    atoms! {
+        fingerprint,
+        mode,
+        key_algorithm,
+        hash_algorithm,
+        creation_time,
+        signature_class
+    }
+}
+
+pub fn transform_new_signature<'a>(env: Env<'a>, signature: NewSignature) -> Result<Term<'a>, Utf8Error> {
+    let fingerprint_atom = atoms::fingerprint().encode(env);
+    let mode_atom = atoms::mode().encode(env);
+    let key_algorithm_atom = atoms::key_algorithm().encode(env);
+    let hash_algorithm_atom = atoms::hash_algorithm().encode(env);
+    let creation_time_atom = atoms::creation_time().encode(env);
+    let signature_class_atom = atoms::signature_class().encode(env);
+
+    let fingerprint = string_or_null!(signature.fingerprint(), env)?;
+    let creation_time = nif_or_nil!(signature.creation_time(), env, content, { content.duration_since(UNIX_EPOCH).expect("time").as_secs() });
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.NewSignature").ok().unwrap()
+            .map_put(fingerprint_atom, fingerprint).ok().unwrap()
+            .map_put(mode_atom, XSignMode(signature.mode()).encode(env)).ok().unwrap()
+            .map_put(key_algorithm_atom, transform_key_algorithm(signature.key_algorithm()).encode(env)).ok().unwrap()
+            .map_put(hash_algorithm_atom, transform_hash_algorithm(signature.hash_algorithm()).encode(env)).ok().unwrap()
+            .map_put(creation_time_atom, creation_time).ok().unwrap()
+            .map_put(signature_class_atom, signature.signature_class().encode(env)).ok().unwrap()
+    )
+}
@@ -0,0 +1,35 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::results::Recipient;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+use key_algorithm::transform_key_algorithm;
+use error::XError;
+
+mod atoms {
+    atoms! {
+        ok,
+        error,
+        key_id,
+        algorithm,
+        status
+    }
+}
+
+pub fn transform_recipient<'a>(env: Env<'a>, recipient: Recipient) -> Result<Term<'a>, Utf8Error> {
+    let key_id_atom = atoms::key_id().encode(env);
+    let algorithm_atom = atoms::algorithm().encode(env);
+    let status_atom = atoms::status().encode(env);
+
+    let key_id = string_or_null!(recipient.key_id(), env)?;
+    let status = match recipient.status() {
+        Ok(_) => atoms::ok().encode(env),
+        Err(error) => (atoms::error(), XError::new(error)).encode(env)
+    };
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.Recipient").ok().unwrap()
+            .map_put(key_id_atom, key_id).ok().unwrap()
+            .map_put(algorithm_atom, transform_key_algorithm(recipient.algorithm()).encode(env)).ok().unwrap()
+            .map_put(status_atom, status).ok().unwrap()
+    )
+}
@@ -10,6 +10,7 @@ use validity::transform_validity;
 use key_algorithm::transform_key_algorithm;
 use hash_algorithm::transform_hash_algorithm;
 use keys::wrap_key;
+use error::XError;
 
 mod atoms {
     atoms! {
@@ -34,13 +35,6 @@ mod atoms {
     }
 }
 
-macro_rules! nif_or_nil {
-    ($expr:expr, $env:ident, $content:ident, $content_to_env:expr) => (match $expr {
-        Some($content) => $content_to_env.encode($env),
-        None => nil().encode($env)
-    });
-}
-
 pub fn transform_signature<'a>(env: Env<'a>, signature: Signature) -> Result<Term<'a>, Utf8Error> {
     let fingerprint_atom = atoms::fingerprint().encode(env);
     let status_atom = atoms::status().encode(env);
@@ -68,7 +62,7 @@ pub fn transform_signature<'a>(env: Env<'a>, signature: Signature) -> Result<Ter
     let expiration_time = nif_or_nil!(signature.expiration_time(), env, content, { content.duration_since(UNIX_EPOCH).expect("time").as_secs() });
     let pka_address = string_or_null!(signature.pka_address(), env)?;
     let nonvalidity_reason = match signature.nonvalidity_reason() {
-        Some(error) => error.description().into_owned().encode(env),
+        Some(error) => XError::new(error).encode(env),
         None => nil().encode(env)
     };
     let policy_url = string_or_null!(signature.policy_url(), env)?;
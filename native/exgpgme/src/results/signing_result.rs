@@ -0,0 +1,56 @@
+use rustler::{Env, Term, Encoder};
+use gpgme::Context;
+use gpgme::results::SigningResult;
+use gpgme_sys as ffi;
+use rustler::types::elixir_struct;
+use std::str::Utf8Error;
+
+use ::results::invalid_key::transform_invalid_key;
+use ::results::new_signature::transform_new_signature;
+
+mod atoms {
+    atoms! {
+        new_signatures,
+        invalid_signers
+    }
+}
+
+pub fn transform_signing_result(env: Env, signing_result: SigningResult) -> Result<Term, Utf8Error> {
+    let new_signatures_atom = atoms::new_signatures().encode(env);
+    let invalid_signers_atom = atoms::invalid_signers().encode(env);
+
+    let new_signatures: Term = signing_result.new_signatures()
+        .map(| signature | {
+            transform_new_signature(env, signature)
+        })
+        .collect::<Result<Vec<Term>, Utf8Error>>()?
+        .encode(env);
+
+    let invalid_signers: Term = signing_result.invalid_signers()
+        .map(| invalid_key | {
+            transform_invalid_key(env, invalid_key)
+        })
+        .collect::<Result<Vec<Term>, Utf8Error>>()?
+        .encode(env);
+
+    Ok(
+        elixir_struct::make_ex_struct(env, "Elixir.ExGpgme.Results.SigningResult").ok().unwrap()
+            .map_put(new_signatures_atom, new_signatures).ok().unwrap()
+            .map_put(invalid_signers_atom, invalid_signers).ok().unwrap()
+    )
+}
+
+
+// gpgme-rs drops the result of a failed operation, but gpgme keeps it on the context.
+pub fn last_signing_result(context: &Context) -> Option<SigningResult> {
+    unsafe {
+        let raw = ffi::gpgme_op_sign_result(context.as_raw());
+
+        if raw.is_null() {
+            None
+        } else {
+            ffi::gpgme_result_ref(raw as *mut _);
+            Some(SigningResult::from_raw(raw))
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use rustler::{Env, Error, NifResult, Term, TermType};
+use rustler::resource::ResourceArc;
+use rustler::types::list::ListIterator;
+use rustler::types::tuple;
+use gpgme;
+use gpgme::{Context, Data, EncryptFlags, SignMode};
+use gpgme::results::{DecryptionResult, EncryptionResult, SigningResult, VerificationResult};
+use gpgme::keys::Key;
+use std::cmp;
+use std::io::{self, Read, Write};
+use std::str::Utf8Error;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use results::verification_result::transform_verification_result;
+use results::decryption_result::transform_decryption_result;
+use results::encryption_result::transform_encryption_result;
+use results::signing_result::transform_signing_result;
+use keys;
+use context::callbacks;
+use context::callbacks::{Callbacks, Messenger, Origin};
+use cancel::CancelHandle;
+use encrypt_flags;
+use sign_mode;
+
+const SESSION_BUFFER_SIZE: usize = 16;
+const SESSION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+pub enum Operation {
+    Encrypt(Vec<Key>, EncryptFlags),
+    Decrypt,
+    Sign(SignMode),
+    Verify
+}
+
+pub enum OperationResult {
+    Encryption(EncryptionResult),
+    Decryption(DecryptionResult),
+    Signing(SigningResult),
+    Verification(VerificationResult)
+}
+
+pub struct SessionState {
+    input: SyncSender<Vec<u8>>,
+    output: Receiver<Vec<u8>>,
+    worker: JoinHandle<gpgme::Result<OperationResult>>
+}
+
+pub struct SessionResource {
+    pub state: Mutex<Option<SessionState>>,
+    pub cancel: Arc<CancelHandle>
+}
+
+struct ChunkReader {
+    receiver: Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+    position: usize,
+    cancel: Arc<CancelHandle>
+}
+
+// Waiting for input is interrupted regularly, gpgme can't notice a cancel while the reader blocks.
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.chunk.len() {
+            self.cancel.check()?;
+
+            match self.receiver.recv_timeout(SESSION_POLL_INTERVAL) {
+                Ok(chunk) => {
+                    self.chunk = chunk;
+                    self.position = 0;
+                },
+                Err(RecvTimeoutError::Timeout) => continue,
+                // All senders are gone, the input is complete.
+                Err(RecvTimeoutError::Disconnected) => return Ok(0)
+            }
+        }
+
+        let length = cmp::min(buf.len(), self.chunk.len() - self.position);
+        buf[..length].copy_from_slice(&self.chunk[self.position..self.position + length]);
+        self.position += length;
+
+        Ok(length)
+    }
+}
+
+struct ChunkWriter {
+    sender: SyncSender<Vec<u8>>
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.sender.send(buf.to_vec()) {
+            Ok(_) => Ok(buf.len()),
+            Err(_) => Err(io::Error::new(io::ErrorKind::BrokenPipe, "session closed"))
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub fn arg_to_operation(arg: Term) -> NifResult<Operation> {
+    match arg.get_type() {
+        TermType::Atom => {
+            match arg.atom_to_string()?.as_ref() {
+                "decrypt" => Ok(Operation::Decrypt),
+                "verify" => Ok(Operation::Verify),
+                _ => Err(Error::BadArg)
+            }
+        },
+        TermType::Tuple => {
+            let tuple = tuple::get_tuple(arg)?;
+            if tuple.is_empty() {
+                return Err(Error::BadArg);
+            }
+
+            let name: String = tuple[0].atom_to_string()?;
+
+            match (name.as_ref(), tuple.len()) {
+                ("encrypt", 3) => {
+                    let key_arcs: Vec<ResourceArc<keys::KeyResource>> = tuple[1].decode()?;
+                    keys::keys_not_empty(key_arcs.len())?;
+
+                    let flags = encrypt_flags::arg_to_protocol(tuple[2].decode::<ListIterator>()?)?;
+
+                    Ok(Operation::Encrypt(key_arcs.iter().map(| key_arc | key_arc.key.clone()).collect(), flags))
+                },
+                ("sign", 2) => Ok(Operation::Sign(sign_mode::arg_to_sign_mode(tuple[1])?)),
+                _ => Err(Error::BadArg)
+            }
+        },
+        _ => Err(Error::BadArg)
+    }
+}
+
+pub fn run_operation(context: &mut Context, operation: Operation, input: &mut Data, output: &mut Data) -> gpgme::Result<OperationResult> {
+    match operation {
+        Operation::Encrypt(recipients, flags) => {
+            let result = context.encrypt_with_flags(&recipients, input, output, flags)?;
+            Ok(OperationResult::Encryption(result))
+        },
+        Operation::Decrypt => {
+            let result = context.decrypt(input, output)?;
+            Ok(OperationResult::Decryption(result))
+        },
+        Operation::Sign(mode) => {
+            let result = context.sign(mode, input, output)?;
+            Ok(OperationResult::Signing(result))
+        },
+        Operation::Verify => {
+            let result = context.verify_opaque(input, output)?;
+            Ok(OperationResult::Verification(result))
+        }
+    }
+}
+
+// Input and output chunks go through bounded channels so neither side runs ahead of the other.
+pub fn spawn_session(mut context: Context, origin: Origin, callbacks: Callbacks, operation: Operation) -> ResourceArc<SessionResource> {
+    let (input_sender, input_receiver) = sync_channel(SESSION_BUFFER_SIZE);
+    let (output_sender, output_receiver) = sync_channel(SESSION_BUFFER_SIZE);
+    let cancel = Arc::new(CancelHandle::running(context.as_raw()));
+    let worker_cancel = cancel.clone();
+
+    let worker = thread::spawn(move || {
+        let reader = ChunkReader{receiver: input_receiver, chunk: Vec::new(), position: 0, cancel: worker_cancel.clone()};
+        let writer = ChunkWriter{sender: output_sender};
+
+        let result = run_session(&mut context, &origin, &callbacks, operation, reader, writer);
+
+        worker_cancel.finish(result)
+    });
+
+    ResourceArc::new(SessionResource{
+        state: Mutex::new(Some(SessionState{
+            input: input_sender,
+            output: output_receiver,
+            worker: worker
+        })),
+        cancel: cancel
+    })
+}
+
+fn run_session(context: &mut Context, origin: &Origin, callbacks: &Callbacks, operation: Operation, reader: ChunkReader, writer: ChunkWriter) -> gpgme::Result<OperationResult> {
+    let mut input = Data::from_reader(reader).map_err(| err | err.error())?;
+    let mut output = Data::from_writer(writer).map_err(| err | err.error())?;
+
+    callbacks::with_callbacks(Messenger::Thread, origin, callbacks, context, | context | {
+        run_operation(context, operation, &mut input, &mut output)
+    })
+}
+
+// A full input channel may mean the worker waits for room in the output channel, so output is taken while retrying.
+// A disconnected input means the worker already stopped; its error is reported by finish.
+pub fn write(state: &SessionState, chunk: &[u8]) -> Vec<u8> {
+    let mut data: Vec<u8> = Vec::new();
+    let mut chunk = chunk.to_vec();
+
+    loop {
+        match state.input.try_send(chunk) {
+            Ok(_) | Err(TrySendError::Disconnected(_)) => break,
+            Err(TrySendError::Full(rest)) => {
+                chunk = rest;
+
+                if let Ok(output) = state.output.recv_timeout(SESSION_POLL_INTERVAL) {
+                    data.extend_from_slice(&output);
+                }
+            }
+        }
+    }
+
+    data.extend_from_slice(&drain_output(&state.output));
+    data
+}
+
+// The output is read until the worker drops its end, a full output channel would block the worker forever.
+pub fn finish(state: SessionState) -> gpgme::Result<(Vec<u8>, OperationResult)> {
+    let SessionState { input, output, worker } = state;
+
+    drop(input);
+
+    let mut data: Vec<u8> = Vec::new();
+
+    while let Ok(chunk) = output.recv() {
+        data.extend_from_slice(&chunk);
+    }
+
+    let result = match worker.join() {
+        Ok(result) => result?,
+        Err(_) => return Err(gpgme::Error::GENERAL)
+    };
+
+    Ok((data, result))
+}
+
+fn drain_output(output: &Receiver<Vec<u8>>) -> Vec<u8> {
+    let mut data: Vec<u8> = Vec::new();
+
+    while let Ok(chunk) = output.try_recv() {
+        data.extend_from_slice(&chunk);
+    }
+
+    data
+}
+
+pub fn transform_operation_result<'a>(env: Env<'a>, result: OperationResult) -> Result<Term<'a>, Utf8Error> {
+    match result {
+        OperationResult::Encryption(result) => transform_encryption_result(env, result),
+        OperationResult::Decryption(result) => transform_decryption_result(env, result),
+        OperationResult::Signing(result) => transform_signing_result(env, result),
+        OperationResult::Verification(result) => transform_verification_result(env, result)
+    }
+}
@@ -1,8 +1,30 @@
 use gpgme::SignMode;
-use rustler::{Term, Error};
+use rustler::{Env, Term, Encoder, Error};
 use rustler::TermType;
 use rustler::types::tuple;
 
+mod atoms {
+    atoms! {
+        normal,
+        detached,
+        clear,
+        other
+    }
+}
+
+pub struct XSignMode(pub SignMode);
+
+impl Encoder for XSignMode {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match &self.0 {
+            SignMode::Normal => atoms::normal().encode(env),
+            SignMode::Detached => atoms::detached().encode(env),
+            SignMode::Clear => atoms::clear().encode(env),
+            SignMode::Other(other) => (atoms::other(), other).encode(env)
+        }
+    }
+}
+
 pub fn arg_to_sign_mode(arg: Term) -> Result<SignMode, Error> {
     match arg.get_type() {
         TermType::Atom => {
@@ -0,0 +1,163 @@
+defmodule ExGpgme.PoolTest do
+  @moduledoc false
+
+  use ExUnit.Case
+  import ExUnit.CaptureLog
+  alias ExGpgme.Context
+  alias ExGpgme.Engine.EngineInfo
+  alias ExGpgme.Pool
+
+  @sender_fingerprint "95E93F470BCB2E96C648572DFBFA85913EE05E95"
+  @sender_secret_key File.read!("priv/test/keys/sender_secret.asc")
+
+  setup do
+    dirname = :erlang.crc32("#{inspect(make_ref())}")
+    path = "priv/test/keychains/#{dirname}"
+
+    File.mkdir!(path)
+    File.chmod!(path, 0o700)
+
+    on_exit(fn ->
+      File.rm_rf!(path)
+    end)
+
+    {:ok, home_dir: path}
+  end
+
+  def start_pool!(opts) do
+    start_supervised!({Pool, opts})
+  end
+
+  describe "start_link/1" do
+    test "rejects sizes below one" do
+      assert_raise ArgumentError, fn -> Pool.start_link(size: 0) end
+    end
+  end
+
+  describe "checkout/3" do
+    test "lends configured contexts", %{home_dir: home_dir} do
+      pool = start_pool!(size: 2, home_dir: home_dir, pinentry_mode: :loopback)
+
+      assert %EngineInfo{home_dir: ^home_dir, protocol: :open_pgp} =
+               Pool.checkout(pool, &Context.engine_info!/1)
+
+      assert :loopback = Pool.checkout(pool, &Context.pinentry_mode/1)
+    end
+
+    test "resets contexts on checkin", %{home_dir: home_dir} do
+      pool = start_pool!(size: 1, home_dir: home_dir, pinentry_mode: :loopback)
+
+      Pool.checkout(pool, fn context ->
+        Context.import!(context, @sender_secret_key)
+        Context.add_signer!(context, Context.find_key!(context, @sender_fingerprint))
+        Context.set_armor(context, true)
+        Context.set_text_mode(context, true)
+        Context.set_pinentry_mode!(context, :cancel)
+      end)
+
+      Pool.checkout(pool, fn context ->
+        assert [] = Context.signers(context)
+        refute Context.armor?(context)
+        refute Context.text_mode?(context)
+        assert :loopback = Context.pinentry_mode(context)
+      end)
+    end
+
+    test "drops flags and home directory of the borrower on checkin", %{home_dir: home_dir} do
+      pool = start_pool!(size: 1)
+
+      Pool.checkout(pool, fn context ->
+        :ok = Context.set_flag(context, "trust-model", "always")
+        Context.set_engine_home_dir!(context, home_dir)
+      end)
+
+      Pool.checkout(pool, fn context ->
+        refute {:ok, "always"} == Context.get_flag(context, "trust-model")
+        refute match?(%EngineInfo{home_dir: ^home_dir}, Context.engine_info!(context))
+      end)
+    end
+
+    test "queues checkouts while all contexts are lent", %{home_dir: home_dir} do
+      pool = start_pool!(size: 1, home_dir: home_dir)
+      test_pid = self()
+
+      holder =
+        spawn_link(fn ->
+          Pool.checkout(pool, fn _context ->
+            send(test_pid, :checked_out)
+
+            receive do
+              :release -> :ok
+            end
+          end)
+        end)
+
+      assert_receive :checked_out
+
+      waiter = Task.async(fn -> Pool.checkout(pool, fn _context -> :done end) end)
+
+      assert nil == Task.yield(waiter, 100)
+
+      send(holder, :release)
+
+      assert :done = Task.await(waiter)
+    end
+
+    test "reclaims contexts of exited processes", %{home_dir: home_dir} do
+      pool = start_pool!(size: 1, home_dir: home_dir)
+
+      # A killed process skips the check-in of `checkout/3`.
+      {pid, monitor} =
+        spawn_monitor(fn ->
+          Pool.checkout(pool, fn _context -> Process.exit(self(), :kill) end)
+        end)
+
+      assert_receive {:DOWN, ^monitor, :process, ^pid, :killed}
+
+      assert :ok = Pool.checkout(pool, fn _context -> :ok end)
+    end
+
+    test "retries failed replacements", %{home_dir: home_dir} do
+      attempts = start_supervised!({Agent, fn -> 0 end})
+
+      # The first replacement after the initial context fails.
+      configure = fn context ->
+        case Agent.get_and_update(attempts, &{&1, &1 + 1}) do
+          1 -> raise "replacement failed"
+          _attempt -> Context.set_armor(context, true)
+        end
+      end
+
+      pool = start_pool!(size: 1, home_dir: home_dir, configure: configure)
+
+      capture_log(fn ->
+        assert Pool.checkout(pool, &Context.armor?/1)
+        assert Pool.checkout(pool, &Context.armor?/1)
+      end)
+    end
+
+    test "exits on timeout without leaking the context", %{home_dir: home_dir} do
+      pool = start_pool!(size: 1, home_dir: home_dir)
+      test_pid = self()
+
+      holder =
+        spawn_link(fn ->
+          Pool.checkout(pool, fn _context ->
+            send(test_pid, :checked_out)
+
+            receive do
+              :release -> :ok
+            end
+          end)
+        end)
+
+      assert_receive :checked_out
+
+      assert {:timeout, _} = catch_exit(Pool.checkout(pool, fn _context -> :ok end, 100))
+
+      send(holder, :release)
+
+      assert :ok = Pool.checkout(pool, fn _context -> :ok end)
+    end
+  end
+end